@@ -0,0 +1,99 @@
+use super::Level;
+use bevy::asset::{AssetLoader, LoadContext, ParseAssetPathError, io::Reader};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+/// The ordered list of levels making up a full game, loaded from a
+/// `.campaign.toml` file in the assets directory.
+#[derive(Asset, TypePath)]
+pub struct Campaign {
+    #[dependency]
+    pub levels: Vec<Handle<Level>>,
+}
+
+/// The layout of a campaign file on disk. Level paths are relative to the
+/// campaign file itself.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CampaignFile {
+    levels: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum CampaignError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    Path(ParseAssetPathError),
+    Empty,
+}
+
+impl fmt::Display for CampaignError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read campaign file: {err}"),
+            Self::Toml(err) => write!(f, "couldn't parse campaign file: {err}"),
+            Self::Path(err) => write!(f, "invalid level path: {err}"),
+            Self::Empty => write!(f, "campaign doesn't list any levels"),
+        }
+    }
+}
+
+impl std::error::Error for CampaignError {}
+
+impl From<std::io::Error> for CampaignError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for CampaignError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
+
+impl From<ParseAssetPathError> for CampaignError {
+    fn from(err: ParseAssetPathError) -> Self {
+        Self::Path(err)
+    }
+}
+
+#[derive(Default)]
+pub struct CampaignLoader;
+
+impl AssetLoader for CampaignLoader {
+    type Asset = Campaign;
+    type Settings = ();
+    type Error = CampaignError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let content = String::from_utf8_lossy(&bytes);
+        let file: CampaignFile = toml::from_str(&content)?;
+
+        if file.levels.is_empty() {
+            return Err(CampaignError::Empty);
+        }
+
+        let mut levels = Vec::with_capacity(file.levels.len());
+
+        for path in &file.levels {
+            let path = load_context.asset_path().resolve_embed(path)?;
+            levels.push(load_context.load(path));
+        }
+
+        Ok(Campaign { levels })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.toml"]
+    }
+}
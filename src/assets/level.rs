@@ -0,0 +1,443 @@
+use crate::components::BrickVariant;
+use crate::constants::*;
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fmt;
+
+/// The character used for a cell with no brick in a level's grid.
+const EMPTY_CELL: char = '0';
+
+/// A single stage, loaded from a `.level.toml` file in the assets directory.
+#[derive(Asset, TypePath)]
+pub struct Level {
+    pub name: String,
+    pub kind: LevelKind,
+    /// The brick layout in row-major order, `BRICK_COLUMN_COUNT` cells wide,
+    /// with `None` for empty cells.
+    pub grid: Vec<Option<BrickVariant>>,
+    pub params: LevelParams,
+}
+
+impl Level {
+    /// Iterates over the bricks in the level along with their row and column.
+    pub fn bricks(&self) -> impl Iterator<Item = (usize, usize, BrickVariant)> + '_ {
+        self.grid.iter().enumerate().filter_map(|(index, cell)| {
+            cell.map(|variant| {
+                (
+                    index / BRICK_COLUMN_COUNT,
+                    index % BRICK_COLUMN_COUNT,
+                    variant,
+                )
+            })
+        })
+    }
+}
+
+/// Whether a level is a regular stage, or a timed bonus stage where the ball
+/// can be lost without losing a life.
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LevelKind {
+    #[default]
+    Stage,
+    Bonus,
+}
+
+/// Optional per-level tweaks, overriding the defaults when present.
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct LevelParams {
+    /// The speed of the ball at each speed tier, slowest first.
+    pub ball_speeds: Option<Vec<f32>>,
+    /// The fastest the paddle may move when steered by the mouse or an
+    /// analog stick.
+    pub max_paddle_speed: Option<f32>,
+    /// Scrolls the brick field sideways, wrapping around the play area.
+    pub scroll: Option<ScrollParams>,
+    /// Steps the brick field down towards the paddle over time.
+    pub descend: Option<DescendParams>,
+    /// The time limit and scoring of a bonus stage. Ignored for regular
+    /// stages.
+    pub bonus: BonusParams,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct BonusParams {
+    /// The number of seconds the player has to clear the stage.
+    pub time_limit: f32,
+    /// The score awarded for each brick destroyed.
+    pub brick_score: u32,
+    /// The score awarded for destroying every brick before time runs out.
+    pub perfect_score: u32,
+}
+
+impl Default for BonusParams {
+    fn default() -> Self {
+        Self {
+            time_limit: BONUS_TIME_LIMIT,
+            brick_score: BONUS_BRICK_SCORE,
+            perfect_score: BONUS_PERFECT_SCORE,
+        }
+    }
+}
+
+#[derive(Deserialize, Default, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollDirection {
+    #[default]
+    Left,
+    Right,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScrollParams {
+    /// The scrolling speed of every row, in logical units per second.
+    pub speed: f32,
+    pub direction: ScrollDirection,
+    /// Speeds for individual rows from the top of the grid, overriding
+    /// `speed`. A negative speed scrolls a row against `direction`.
+    pub rows: Vec<f32>,
+}
+
+/// How often the brick field steps down a row. Either or both of the interval
+/// and paddle hit count can be given, whichever comes first triggers a step.
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct DescendParams {
+    /// The number of seconds of play between each step.
+    pub interval: Option<f32>,
+    /// The number of paddle hits between each step.
+    pub paddle_hits: Option<u32>,
+}
+
+impl ScrollParams {
+    /// The horizontal velocity of the bricks in the given row.
+    pub fn row_velocity(&self, row: usize) -> f32 {
+        let speed = self.rows.get(row).copied().unwrap_or(self.speed);
+
+        match self.direction {
+            ScrollDirection::Left => -speed,
+            ScrollDirection::Right => speed,
+        }
+    }
+}
+
+/// The layout of a level file on disk, before the grid has been validated.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LevelFile {
+    name: String,
+    #[serde(default)]
+    kind: LevelKind,
+    grid: Vec<String>,
+    #[serde(default)]
+    params: LevelParams,
+}
+
+#[derive(Debug)]
+pub enum LevelError {
+    Io(std::io::Error),
+    Toml(toml::de::Error),
+    RowCount {
+        found: usize,
+    },
+    RowLength {
+        row: usize,
+        found: usize,
+    },
+    UnknownBrick {
+        row: usize,
+        column: usize,
+        cell: char,
+    },
+    /// A parameter that must be a positive, finite number, such as a time
+    /// or speed, was zero, negative or not a number.
+    NotPositive {
+        param: &'static str,
+        value: f64,
+    },
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read level file: {err}"),
+            Self::Toml(err) => write!(f, "couldn't parse level file: {err}"),
+            Self::RowCount { found } => write!(
+                f,
+                "expected a grid of {BRICK_ROW_COUNT} rows, but found {found}"
+            ),
+            Self::RowLength { row, found } => write!(
+                f,
+                "row {row}: expected {BRICK_COLUMN_COUNT} cells, but found {found}"
+            ),
+            Self::UnknownBrick { row, column, cell } => {
+                write!(f, "row {row}, column {column}: unknown brick '{cell}'")
+            }
+            Self::NotPositive { param, value } => {
+                write!(f, "{param}: expected a positive number, but found {value}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelError {}
+
+impl From<std::io::Error> for LevelError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<toml::de::Error> for LevelError {
+    fn from(err: toml::de::Error) -> Self {
+        Self::Toml(err)
+    }
+}
+
+/// Rejects a parameter that isn't a positive, finite number.
+fn check_positive(param: &'static str, value: impl Into<f64>) -> Result<(), LevelError> {
+    let value = value.into();
+
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(LevelError::NotPositive { param, value })
+    }
+}
+
+impl LevelParams {
+    fn validate(&self) -> Result<(), LevelError> {
+        if let Some(max_paddle_speed) = self.max_paddle_speed {
+            check_positive("params.max_paddle_speed", max_paddle_speed)?;
+        }
+
+        if let Some(descend) = &self.descend {
+            if let Some(interval) = descend.interval {
+                check_positive("params.descend.interval", interval)?;
+            }
+
+            if let Some(paddle_hits) = descend.paddle_hits {
+                check_positive("params.descend.paddle_hits", paddle_hits)?;
+            }
+        }
+
+        check_positive("params.bonus.time_limit", self.bonus.time_limit)?;
+
+        Ok(())
+    }
+}
+
+impl TryFrom<LevelFile> for Level {
+    type Error = LevelError;
+
+    fn try_from(file: LevelFile) -> Result<Self, Self::Error> {
+        if file.grid.len() != BRICK_ROW_COUNT {
+            return Err(LevelError::RowCount {
+                found: file.grid.len(),
+            });
+        }
+
+        let mut grid = Vec::with_capacity(BRICK_COUNT_TOTAL);
+
+        // Rows and columns are reported 1-indexed, to match what a level
+        // author sees in their editor.
+        for (row, cells) in file.grid.iter().enumerate() {
+            let row = row + 1;
+            let found = cells.chars().count();
+
+            if found != BRICK_COLUMN_COUNT {
+                return Err(LevelError::RowLength { row, found });
+            }
+
+            for (column, cell) in cells.chars().enumerate() {
+                if cell == EMPTY_CELL {
+                    grid.push(None);
+                    continue;
+                }
+
+                let variant = cell
+                    .to_digit(10)
+                    .and_then(|code| BrickVariant::from_u8(code as u8))
+                    .ok_or(LevelError::UnknownBrick {
+                        row,
+                        column: column + 1,
+                        cell,
+                    })?;
+
+                grid.push(Some(variant));
+            }
+        }
+
+        file.params.validate()?;
+
+        Ok(Self {
+            name: file.name,
+            kind: file.kind,
+            grid,
+            params: file.params,
+        })
+    }
+}
+
+#[derive(Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    type Asset = Level;
+    type Settings = ();
+    type Error = LevelError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let content = String::from_utf8_lossy(&bytes);
+        let file: LevelFile = toml::from_str(&content)?;
+
+        Level::try_from(file)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.toml"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level_file(grid: Vec<String>) -> LevelFile {
+        LevelFile {
+            name: String::from("Test"),
+            kind: LevelKind::default(),
+            grid,
+            params: LevelParams::default(),
+        }
+    }
+
+    fn empty_grid() -> Vec<String> {
+        vec![EMPTY_CELL.to_string().repeat(BRICK_COLUMN_COUNT); BRICK_ROW_COUNT]
+    }
+
+    #[test]
+    fn valid_grid_is_loaded() {
+        let mut grid = empty_grid();
+        grid[1].replace_range(2..3, "2");
+
+        let level = Level::try_from(level_file(grid)).unwrap();
+
+        assert_eq!(level.grid.len(), BRICK_COUNT_TOTAL);
+        assert_eq!(
+            level.bricks().collect::<Vec<_>>(),
+            [(1, 2, BrickVariant::MultiHit)]
+        );
+    }
+
+    #[test]
+    fn wrong_number_of_rows_is_rejected() {
+        let mut grid = empty_grid();
+        grid.pop();
+
+        assert!(matches!(
+            Level::try_from(level_file(grid)),
+            Err(LevelError::RowCount { found }) if found == BRICK_ROW_COUNT - 1
+        ));
+    }
+
+    #[test]
+    fn wrong_row_length_is_rejected() {
+        let mut grid = empty_grid();
+        grid[2].push(EMPTY_CELL);
+
+        assert!(matches!(
+            Level::try_from(level_file(grid)),
+            Err(LevelError::RowLength { row: 3, found }) if found == BRICK_COLUMN_COUNT + 1
+        ));
+    }
+
+    #[test]
+    fn unknown_brick_is_rejected() {
+        let mut grid = empty_grid();
+        grid[4].replace_range(6..7, "x");
+
+        assert!(matches!(
+            Level::try_from(level_file(grid)),
+            Err(LevelError::UnknownBrick {
+                row: 5,
+                column: 7,
+                cell: 'x',
+            })
+        ));
+    }
+
+    #[test]
+    fn non_positive_descend_params_are_rejected() {
+        let mut file = level_file(empty_grid());
+        file.params.descend = Some(DescendParams {
+            interval: Some(-1.0),
+            paddle_hits: None,
+        });
+
+        assert!(matches!(
+            Level::try_from(file),
+            Err(LevelError::NotPositive {
+                param: "params.descend.interval",
+                ..
+            })
+        ));
+
+        let mut file = level_file(empty_grid());
+        file.params.descend = Some(DescendParams {
+            interval: Some(10.0),
+            paddle_hits: Some(0),
+        });
+
+        assert!(matches!(
+            Level::try_from(file),
+            Err(LevelError::NotPositive {
+                param: "params.descend.paddle_hits",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn non_positive_max_paddle_speed_is_rejected() {
+        let mut file = level_file(empty_grid());
+        file.params.max_paddle_speed = Some(-160.0);
+
+        assert!(matches!(
+            Level::try_from(file),
+            Err(LevelError::NotPositive {
+                param: "params.max_paddle_speed",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn non_positive_bonus_time_limit_is_rejected() {
+        for time_limit in [0.0, -5.0, f32::NAN] {
+            let mut file = level_file(empty_grid());
+            file.kind = LevelKind::Bonus;
+            file.params.bonus.time_limit = time_limit;
+
+            assert!(matches!(
+                Level::try_from(file),
+                Err(LevelError::NotPositive {
+                    param: "params.bonus.time_limit",
+                    ..
+                })
+            ));
+        }
+    }
+}
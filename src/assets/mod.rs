@@ -0,0 +1,5 @@
+mod campaign;
+mod level;
+
+pub use campaign::*;
+pub use level::*;
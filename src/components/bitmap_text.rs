@@ -0,0 +1,70 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// One of the four tones of a DMG display.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum DmgTone {
+    Lightest,
+    Light,
+    Dark,
+    #[default]
+    Darkest,
+}
+
+impl DmgTone {
+    pub fn color(&self) -> Color {
+        match self {
+            Self::Lightest => DMG_COLOR_0,
+            Self::Light => DMG_COLOR_1,
+            Self::Dark => DMG_COLOR_2,
+            Self::Darkest => DMG_COLOR_3,
+        }
+    }
+}
+
+/// Which part of each line of text sits at the entity's position.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Text drawn with the bitmap font, one glyph sprite per character, laid out
+/// on the pixel grid. Lines are separated by `\n`, and the entity's position
+/// is the vertical centre of the first line.
+#[derive(Component, Clone, Default, PartialEq, Eq)]
+#[require(Transform, Visibility)]
+pub struct BitmapText {
+    pub text: String,
+    pub tone: DmgTone,
+    pub align: TextAlign,
+}
+
+impl BitmapText {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..default()
+        }
+    }
+
+    pub fn with_tone(mut self, tone: DmgTone) -> Self {
+        self.tone = tone;
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Formats a number as a fixed-width field, padded with leading zeroes.
+    /// Numbers too large to fit are capped at the largest that does, so the
+    /// field never grows.
+    pub fn number(value: u32, digits: usize) -> String {
+        let max = 10u32.saturating_pow(digits as u32).saturating_sub(1);
+        format!("{:0digits$}", value.min(max))
+    }
+}
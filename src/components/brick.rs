@@ -4,10 +4,21 @@ use bevy::prelude::*;
 
 const NORMAL: u8 = 1;
 const MULTIHIT: u8 = 2;
+const UNBREAKABLE: u8 = 3;
+const HIDDEN: u8 = 4;
+const EXPLOSIVE: u8 = 5;
 
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum BrickVariant {
     Normal,
     MultiHit,
+    /// Can't be destroyed, and doesn't need to be for the stage to be
+    /// cleared.
+    Unbreakable,
+    /// Invisible until the ball first hits it.
+    Hidden,
+    /// Destroys the bricks surrounding it when destroyed.
+    Explosive,
 }
 
 impl BrickVariant {
@@ -15,6 +26,9 @@ impl BrickVariant {
         match value {
             NORMAL => Some(Self::Normal),
             MULTIHIT => Some(Self::MultiHit),
+            UNBREAKABLE => Some(Self::Unbreakable),
+            HIDDEN => Some(Self::Hidden),
+            EXPLOSIVE => Some(Self::Explosive),
             _ => None,
         }
     }
@@ -23,20 +37,67 @@ impl BrickVariant {
         match self {
             Self::Normal => BRICK_NORMAL_SPRITE_PATH,
             Self::MultiHit => BRICK_MULTIHIT_SPRITE_PATH,
+            Self::Unbreakable => BRICK_UNBREAKABLE_SPRITE_PATH,
+            Self::Hidden => BRICK_HIDDEN_SPRITE_PATH,
+            Self::Explosive => BRICK_EXPLOSIVE_SPRITE_PATH,
+        }
+    }
+
+    /// The sprite to swap to once a brick has taken a hit but survived, if the
+    /// variant can survive a hit at all.
+    pub fn damaged_asset_path(&self) -> Option<&str> {
+        match self {
+            Self::MultiHit => Some(BRICK_MULTIHIT_DAMAGED_SPRITE_PATH),
+            _ => None,
+        }
+    }
+
+    /// The number of hits the brick takes to destroy, or `None` if it can't be
+    /// destroyed. A hidden brick's first hit only reveals it.
+    pub fn hit_points(&self) -> Option<u8> {
+        match self {
+            Self::Normal | Self::Explosive => Some(1),
+            Self::MultiHit | Self::Hidden => Some(2),
+            Self::Unbreakable => None,
+        }
+    }
+
+    /// The score awarded for destroying the brick.
+    pub fn points(&self) -> u32 {
+        match self {
+            Self::Normal => 10,
+            Self::MultiHit => 30,
+            Self::Unbreakable => 0,
+            Self::Hidden => 50,
+            Self::Explosive => 20,
+        }
+    }
+
+    fn visibility(&self) -> Visibility {
+        match self {
+            Self::Hidden => Visibility::Hidden,
+            _ => Visibility::Inherited,
         }
     }
 }
 
-#[derive(Component, Default)]
+/// The number of hits a brick can take before it is destroyed. Bricks without
+/// hit points can't be destroyed.
+#[derive(Component, Deref, DerefMut)]
+pub struct HitPoints(pub u8);
+
+#[derive(Component)]
 #[require(Sprite, Transform, Collider)]
-pub struct Brick;
+pub struct Brick {
+    pub variant: BrickVariant,
+}
 
 impl Brick {
     pub fn new(
         variant: BrickVariant,
         position: Vec2,
         asset_server: &Res<AssetServer>,
-    ) -> (Brick, Sprite, Transform, Collider) {
+    ) -> (Brick, Sprite, Transform, Visibility, Collider) {
         let sprite = Sprite {
             image: asset_server.load(variant.asset_path()),
             custom_size: Some(Vec2::ONE),
@@ -46,6 +107,12 @@ impl Brick {
         let transform =
             Transform::from_translation(position.extend(0.)).with_scale(BRICK_SIZE.extend(1.));
 
-        (Brick, sprite, transform, Collider)
+        (
+            Brick { variant },
+            sprite,
+            transform,
+            variant.visibility(),
+            Collider,
+        )
     }
 }
@@ -0,0 +1,21 @@
+use super::{BitmapText, TextAlign};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Text in the HUD showing the time left to clear a bonus stage.
+#[derive(Component)]
+pub struct Countdown;
+
+impl Countdown {
+    pub fn new(seconds: u32) -> (Countdown, BitmapText, Transform) {
+        (
+            Countdown,
+            BitmapText::new(Self::text(seconds)).with_align(TextAlign::Center),
+            Transform::from_translation(COUNTDOWN_POSITION.extend(0.)),
+        )
+    }
+
+    pub fn text(seconds: u32) -> String {
+        format!("TIME\n{}", BitmapText::number(seconds, HUD_COUNTER_DIGITS))
+    }
+}
@@ -0,0 +1,35 @@
+use super::{BitmapText, Panel, TextAlign};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The panel shown over the play area once the game is over.
+#[derive(Component)]
+pub struct GameOverPanel;
+
+impl GameOverPanel {
+    pub fn new() -> (GameOverPanel, (Panel, Sprite, Transform)) {
+        (
+            GameOverPanel,
+            Panel::new(GAME_OVER_PANEL_POSITION, GAME_OVER_PANEL_SIZE),
+        )
+    }
+}
+
+/// The text on the game over panel, a child of the panel.
+#[derive(Component)]
+pub struct GameOverText;
+
+impl GameOverText {
+    pub fn new(text: String) -> (GameOverText, BitmapText, Transform) {
+        (
+            GameOverText,
+            BitmapText::new(text).with_align(TextAlign::Center),
+            Transform::from_translation(GAME_OVER_TEXT_OFFSET.extend(1.)),
+        )
+    }
+
+    /// Asks for the initials of a new high score, showing those entered so far.
+    pub fn initials_prompt(initials: &str) -> String {
+        format!("GAME OVER\n\nHIGH SCORE!\n\nENTER NAME\n\n{initials}")
+    }
+}
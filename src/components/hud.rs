@@ -0,0 +1,73 @@
+use super::{BitmapText, DmgTone, TextAlign};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Marks every entity making up the HUD side panel.
+#[derive(Component)]
+pub struct Hud;
+
+/// A HUD value, kept up to date with the resource it shows.
+#[derive(Component, Clone, Copy)]
+pub enum HudField {
+    HighScore,
+    Score,
+    Stage,
+    Lives,
+}
+
+impl HudField {
+    pub const ALL: [HudField; 4] = [Self::HighScore, Self::Score, Self::Stage, Self::Lives];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::HighScore => "TOP",
+            Self::Score => "SCORE",
+            Self::Stage => "STAGE",
+            Self::Lives => "BALLS",
+        }
+    }
+
+    fn position(&self) -> Vec2 {
+        match self {
+            Self::HighScore => HUD_HIGH_SCORE_POSITION,
+            Self::Score => HUD_SCORE_POSITION,
+            Self::Stage => HUD_STAGE_POSITION,
+            Self::Lives => HUD_LIVES_POSITION,
+        }
+    }
+
+    /// The fixed number of digits the value is shown with.
+    pub fn digits(&self) -> usize {
+        match self {
+            Self::HighScore | Self::Score => HUD_SCORE_DIGITS,
+            Self::Stage | Self::Lives => HUD_COUNTER_DIGITS,
+        }
+    }
+}
+
+impl Hud {
+    pub fn label(field: HudField) -> (Hud, BitmapText, Transform) {
+        (
+            Hud,
+            BitmapText::new(field.label()).with_align(TextAlign::Center),
+            Transform::from_translation(field.position().extend(0.)),
+        )
+    }
+
+    pub fn value(field: HudField) -> (Hud, HudField, BitmapText, Transform) {
+        // Right-aligned to the edge of the widest value, so that the digits
+        // of every value line up in columns.
+        let mut position = field.position();
+        position.x += HUD_SCORE_DIGITS as f32 * FONT_GLYPH_SIZE / 2.;
+        position.y -= HUD_VALUE_OFFSET;
+
+        (
+            Hud,
+            field,
+            BitmapText::default()
+                .with_tone(DmgTone::Dark)
+                .with_align(TextAlign::Right),
+            Transform::from_translation(position.extend(0.)),
+        )
+    }
+}
@@ -1,13 +1,27 @@
 mod ball;
+mod bitmap_text;
 mod brick;
 mod collider;
+mod countdown;
+mod game_over;
+mod hud;
 mod paddle;
+mod panel;
+mod pause_menu;
+mod title;
 mod velocity;
 mod wall;
 
 pub use ball::*;
+pub use bitmap_text::*;
 pub use brick::*;
 pub use collider::*;
+pub use countdown::*;
+pub use game_over::*;
+pub use hud::*;
 pub use paddle::*;
+pub use panel::*;
+pub use pause_menu::*;
+pub use title::*;
 pub use velocity::*;
 pub use wall::*;
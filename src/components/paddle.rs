@@ -1,13 +1,13 @@
-use super::Collider;
+use super::{Collider, Velocity};
 use crate::constants::*;
 use bevy::prelude::*;
 
 #[derive(Component)]
-#[require(Sprite, Transform, Collider)]
+#[require(Sprite, Transform, Collider, Velocity)]
 pub struct Paddle;
 
 impl Paddle {
-    pub fn new(asset_server: &AssetServer) -> (Paddle, Sprite, Transform, Collider) {
+    pub fn new(asset_server: &AssetServer) -> (Paddle, Sprite, Transform, Collider, Velocity) {
         let sprite = Sprite {
             image: asset_server.load(PADDLE_SPRITE_PATH),
             custom_size: Some(Vec2::ONE),
@@ -17,6 +17,11 @@ impl Paddle {
         let transform = Transform::from_translation(PADDLE_START_POSITION.extend(0.))
             .with_scale(PADDLE_SIZE.extend(1.));
 
-        (Paddle, sprite, transform, Collider)
+        (Paddle, sprite, transform, Collider, Velocity::default())
     }
 }
+
+/// Marks the paddle while it is shrunk, after the ball has reached the top
+/// wall.
+#[derive(Component)]
+pub struct Shrunk;
@@ -0,0 +1,20 @@
+use super::DmgTone;
+use bevy::prelude::*;
+
+/// A plain box drawn over the play field, for menus and messages to sit on.
+#[derive(Component)]
+#[require(Sprite, Transform)]
+pub struct Panel;
+
+impl Panel {
+    pub fn new(position: Vec2, size: Vec2) -> (Panel, Sprite, Transform) {
+        // Drawn above the walls, so that it hides whatever part of the play
+        // field it covers, and a tone darker than the background so that it
+        // stands out from the empty play field around it.
+        (
+            Panel,
+            Sprite::from_color(DmgTone::Light.color(), size),
+            Transform::from_translation(position.extend(2.)),
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use super::{BitmapText, Panel, TextAlign};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The panel shown over the play field while the game is paused.
+#[derive(Component)]
+pub struct PausePanel;
+
+impl PausePanel {
+    pub fn new() -> (PausePanel, (Panel, Sprite, Transform)) {
+        (
+            PausePanel,
+            Panel::new(PAUSE_PANEL_POSITION, PAUSE_PANEL_SIZE),
+        )
+    }
+
+    pub fn title() -> (BitmapText, Transform) {
+        (
+            BitmapText::new("PAUSED").with_align(TextAlign::Center),
+            Transform::from_translation(PAUSE_TITLE_OFFSET.extend(1.)),
+        )
+    }
+}
+
+/// The list of pause menu options, a child of the pause panel.
+#[derive(Component)]
+pub struct PauseMenuText;
+
+impl PauseMenuText {
+    pub fn new(text: String) -> (PauseMenuText, BitmapText, Transform) {
+        (
+            PauseMenuText,
+            BitmapText::new(text),
+            Transform::from_translation(PAUSE_OPTIONS_OFFSET.extend(1.)),
+        )
+    }
+}
@@ -0,0 +1,23 @@
+use super::BitmapText;
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The game's logo, shown at the top of the title screens.
+#[derive(Component)]
+#[require(Sprite, Transform)]
+pub struct Logo;
+
+impl Logo {
+    pub fn new(asset_server: &AssetServer) -> (Logo, Sprite, Transform) {
+        (
+            Logo,
+            Sprite::from_image(asset_server.load(LOGO_SPRITE_PATH)),
+            Transform::from_translation(LOGO_POSITION.extend(0.)),
+        )
+    }
+}
+
+/// The text beneath the logo, which each title screen replaces with its own.
+#[derive(Component)]
+#[require(BitmapText)]
+pub struct TitleText;
@@ -1,11 +1,4 @@
-use crate::constants::*;
 use bevy::prelude::*;
 
-#[derive(Component, Deref, DerefMut)]
+#[derive(Component, Default, Deref, DerefMut)]
 pub struct Velocity(pub Vec2);
-
-impl Default for Velocity {
-    fn default() -> Self {
-        Self(Vec2::new(BALL_SPEED, BALL_SPEED))
-    }
-}
@@ -69,6 +69,11 @@ impl WallLocation {
 #[require(Sprite, Transform)]
 pub struct Wall;
 
+/// Marks the top wall, which the ball can only reach by breaking through the
+/// bricks.
+#[derive(Component)]
+pub struct Ceiling;
+
 impl Wall {
     pub fn new(
         location: WallLocation,
@@ -88,7 +93,9 @@ impl Wall {
             ..default()
         };
 
-        let transform = Transform::from_translation(location.position().extend(0.))
+        // Walls are drawn above the bricks, so that scrolling bricks slide
+        // beneath them as they wrap around the play area.
+        let transform = Transform::from_translation(location.position().extend(1.))
             .with_scale(location.size().extend(1.));
 
         (Wall, sprite, transform)
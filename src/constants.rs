@@ -4,6 +4,20 @@ use std::f32::consts::PI;
 /// Path to a TOML file containing defined keybindings.
 pub const KEYBINDINGS_PATH: &str = "keybindings.toml";
 
+/// How often in seconds the keybindings file is checked for changes, so that
+/// edits to it take effect without restarting the game.
+pub const KEYBINDINGS_POLL_INTERVAL: f32 = 1.;
+
+/// How far a gamepad axis must be pushed before it counts as pressed, unless
+/// the keybindings say otherwise.
+pub const GAMEPAD_DEADZONE: f32 = 0.5;
+
+/// Path to a TOML file the high score table is saved to.
+pub const HIGH_SCORES_PATH: &str = "high_scores.toml";
+
+/// Assets directory path for the campaign listing the levels of a full game.
+pub const CAMPAIGN_PATH: &str = "levels/main.campaign.toml";
+
 /// The pre-scaled width of the game window.
 pub const DMG_WIDTH: f32 = 160.;
 
@@ -102,12 +116,47 @@ pub const BRICK_NORMAL_SPRITE_PATH: &str = "sprites/brick-normal.png";
 /// Assets directory path for the multihit brick sprite.
 pub const BRICK_MULTIHIT_SPRITE_PATH: &str = "sprites/brick-multihit.png";
 
+/// Assets directory path for the multihit brick sprite, once it has been hit.
+pub const BRICK_MULTIHIT_DAMAGED_SPRITE_PATH: &str = "sprites/brick-multihit-cracked.png";
+
+/// Assets directory path for the unbreakable brick sprite.
+pub const BRICK_UNBREAKABLE_SPRITE_PATH: &str = "sprites/brick-unbreakable.png";
+
+/// Assets directory path for the hidden brick sprite, shown once it is hit.
+pub const BRICK_HIDDEN_SPRITE_PATH: &str = "sprites/brick-hidden.png";
+
+/// Assets directory path for the explosive brick sprite.
+pub const BRICK_EXPLOSIVE_SPRITE_PATH: &str = "sprites/brick-explosive.png";
+
+/// Assets directory path for the logo shown on the title screens.
+pub const LOGO_SPRITE_PATH: &str = "sprites/logo.png";
+
 /// Assets directory path for the paddle sprite.
 pub const PADDLE_SPRITE_PATH: &str = "sprites/paddle.png";
 
+/// Assets directory path for the shrunk paddle sprite.
+pub const PADDLE_SHRUNK_SPRITE_PATH: &str = "sprites/paddle-small.png";
+
 /// Assets directory path for the ball sprite.
 pub const BALL_SPRITE_PATH: &str = "sprites/ball.png";
 
+/// Assets directory path for the bitmap font sprite sheet.
+pub const FONT_SPRITE_PATH: &str = "sprites/font.png";
+
+/// The length and width of a glyph in the bitmap font, which is also the
+/// spacing between characters and lines of text.
+pub const FONT_GLYPH_SIZE: f32 = 8.;
+
+/// The number of glyph columns in the bitmap font sprite sheet.
+pub const FONT_COLUMNS: u32 = 16;
+
+/// The number of glyph rows in the bitmap font sprite sheet.
+pub const FONT_ROWS: u32 = 4;
+
+/// The character of the first glyph in the bitmap font sprite sheet. The
+/// rest follow in ASCII order.
+pub const FONT_FIRST_CHARACTER: char = ' ';
+
 /// The length and width of a breakable brick.
 pub const BRICK_SIZE: Vec2 = Vec2::new(8., 4.);
 
@@ -126,6 +175,13 @@ pub const BRICK_SPAWN_LOCATION: Vec2 = Vec2::new(
     SCREEN_TOP - WALL_TILE_SIZE - BRICK_SIZE.y / 2.,
 );
 
+/// The left-most edge of the brick field, which scrolling bricks wrap around.
+pub const BRICK_FIELD_LEFT: f32 = BRICK_SPAWN_LOCATION.x - BRICK_SIZE.x / 2.;
+
+/// The total width of the brick field, which spans the play area between the
+/// left and right walls.
+pub const BRICK_FIELD_WIDTH: f32 = BRICK_COLUMN_COUNT as f32 * BRICK_SIZE.x;
+
 /// The length and width of the player paddle.
 pub const PADDLE_SIZE: Vec2 = Vec2::new(26., 5.);
 
@@ -138,48 +194,173 @@ pub const PADDLE_START_POSITION: Vec2 = Vec2::new(
     SCREEN_BOTTOM + PADDLE_BOTTOM_PADDING + PADDLE_SIZE.y / 2.,
 );
 
-/// Left-most constraint for the paddle's movement.
-pub const PADDLE_LEFT_BOUND: f32 = WALL_LOCATION_LEFT.x + HALF_WALL_TILE + PADDLE_SIZE.x / 2.;
+/// The length and width of the player paddle after it has been shrunk by the
+/// ball reaching the top wall.
+pub const PADDLE_SHRUNK_SIZE: Vec2 = Vec2::new(13., PADDLE_SIZE.y);
 
-/// Right-most constraint for the paddle's movement.
-pub const PADDLE_RIGHT_BOUND: f32 = WALL_LOCATION_RIGHT.x - HALF_WALL_TILE - PADDLE_SIZE.x / 2.;
+/// Left-most constraint for the paddle's left edge. The paddle's centre is
+/// kept half of its current width inside this bound.
+pub const PADDLE_LEFT_BOUND: f32 = WALL_LOCATION_LEFT.x + HALF_WALL_TILE;
+
+/// Right-most constraint for the paddle's right edge. The paddle's centre is
+/// kept half of its current width inside this bound.
+pub const PADDLE_RIGHT_BOUND: f32 = WALL_LOCATION_RIGHT.x - HALF_WALL_TILE;
 
 /// The movement speed of the player paddle.
 pub const PADDLE_SPEED: f32 = 80.0;
 
-/// The movement speed of the ball.
-pub const BALL_SPEED: f32 = 50.0;
+/// The fastest the paddle may chase the mouse or an analog stick, unless the
+/// level says otherwise, so that flicking it across the screen can't fling the
+/// ball at an unfair speed.
+pub const PADDLE_MAX_SPEED: f32 = 160.0;
+
+/// The gamepad axis whose position the paddle follows when it is steered
+/// with an analog stick.
+pub const PADDLE_ANALOG_AXIS: GamepadAxis = GamepadAxis::LeftStickX;
+
+/// The angles in radians, measured anticlockwise from the positive x axis,
+/// that the ball rebounds at from each zone of the paddle. Zones run from the
+/// left edge of the paddle to the right, and the outermost zones are kept well
+/// clear of horizontal.
+pub const PADDLE_REBOUND_ANGLES: [f32; 6] = [
+    PI * 5. / 6.,
+    PI * 3. / 4.,
+    PI * 2. / 3.,
+    PI / 3.,
+    PI / 4.,
+    PI / 6.,
+];
+
+/// The movement speed of the ball at each speed tier, slowest first. The ball
+/// starts each life at the first tier.
+pub const BALL_SPEED_TIERS: [f32; 4] = [70., 85., 100., 120.];
 
 /// The width and length of the ball.
 pub const BALL_SIZE: f32 = 4.;
 
-/// The starting position for the ball.
+/// The starting position for the ball, resting on top of the paddle.
 pub const BALL_START_POSITION: Vec2 = Vec2::new(
     PADDLE_START_POSITION.x,
-    PADDLE_START_POSITION.y + PADDLE_SIZE.y / 2.,
+    PADDLE_START_POSITION.y + PADDLE_SIZE.y / 2. + BALL_SIZE / 2.,
 );
 
-/// Hardcoded tilesheet representing a basic debug level used
-/// for testing game mechanics. This level is not loaded during
-/// typical gameplay.
-#[rustfmt::skip]
-pub const DEBUG_LEVEL: [u8; BRICK_COUNT_TOTAL] = [
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 2, 2, 2, 2, 2, 2 ,2 ,2 ,2 ,2 ,2 ,0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 2, 2, 2, 2, 2, 2 ,2 ,2 ,2 ,2 ,2 ,0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 2, 2, 2, 2, 2, 2 ,2 ,2 ,2 ,2 ,2 ,0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0,
-    0, 2, 2, 2, 2, 2, 2 ,2 ,2 ,2 ,2 ,2 ,0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
-];
+/// The height below which the ball is considered lost, once it has fallen
+/// past the paddle and out of the bottom of the screen.
+pub const BALL_DEATH_ZONE: f32 = SCREEN_BOTTOM - BALL_SIZE / 2.;
+
+/// The number of lives the player starts a new game with.
+pub const STARTING_LIVES: u32 = 5;
+
+/// The delay in seconds after losing the ball before a new one is parked on
+/// the paddle.
+pub const RESPAWN_DELAY: f32 = 1.5;
+
+/// The delay in seconds after clearing a stage before the next is spawned.
+pub const STAGE_CLEAR_DELAY: f32 = 2.;
+
+/// The number of consecutive brick hits, before the ball returns to the
+/// paddle, needed to raise the score multiplier by one.
+pub const COMBO_HITS_PER_STEP: u32 = 4;
+
+/// The highest score multiplier a combo can reach.
+pub const COMBO_MAX_MULTIPLIER: u32 = 4;
+
+/// The scores at which the player is awarded an extra life.
+pub const EXTRA_LIFE_SCORES: [u32; 4] = [1000, 3000, 6000, 10000];
+
+/// The default number of seconds the player has to clear a bonus stage.
+pub const BONUS_TIME_LIMIT: f32 = 30.;
+
+/// The default score awarded for each brick destroyed in a bonus stage.
+pub const BONUS_BRICK_SCORE: u32 = 10;
+
+/// The default score awarded for clearing every brick in a bonus stage.
+pub const BONUS_PERFECT_SCORE: u32 = 1000;
+
+/// The horizontal centre of the HUD, which fills the strip of the screen to
+/// the right of the play area.
+pub const HUD_CENTER: f32 = (WALL_LOCATION_RIGHT.x + HALF_WALL_TILE + SCREEN_RIGHT) / 2.;
+
+/// The vertical distance between a HUD label and the value beneath it.
+pub const HUD_VALUE_OFFSET: f32 = 9.;
+
+/// The number of digits shown for the score and high score in the HUD.
+pub const HUD_SCORE_DIGITS: usize = 5;
+
+/// The number of digits shown for the stage number and lives in the HUD.
+pub const HUD_COUNTER_DIGITS: usize = 2;
+
+/// Where the high score is shown in the HUD.
+pub const HUD_HIGH_SCORE_POSITION: Vec2 = Vec2::new(HUD_CENTER, 58.);
+
+/// Where the score is shown in the HUD.
+pub const HUD_SCORE_POSITION: Vec2 = Vec2::new(HUD_CENTER, 34.);
+
+/// Where the stage number is shown in the HUD.
+pub const HUD_STAGE_POSITION: Vec2 = Vec2::new(HUD_CENTER, 10.);
+
+/// Where the remaining lives are shown in the HUD.
+pub const HUD_LIVES_POSITION: Vec2 = Vec2::new(HUD_CENTER, -14.);
+
+/// Where the bonus stage countdown is shown in the HUD.
+pub const COUNTDOWN_POSITION: Vec2 = Vec2::new(HUD_CENTER, -38.);
+
+/// The number of scores kept in the high score table.
+pub const HIGH_SCORE_COUNT: usize = 10;
+
+/// The number of letters in the initials entered for a high score.
+pub const INITIALS_LENGTH: usize = 3;
+
+/// The size of the panel shown over the play area once the game is over.
+pub const GAME_OVER_PANEL_SIZE: Vec2 = Vec2::new(BRICK_FIELD_WIDTH, 104.);
+
+/// Where the panel shown once the game is over is centred.
+pub const GAME_OVER_PANEL_POSITION: Vec2 = Vec2::new(PLAY_AREA_CENTER, 0.);
+
+/// Where the first line of the game over panel's text sits, relative to the
+/// centre of the panel.
+pub const GAME_OVER_TEXT_OFFSET: Vec2 =
+    Vec2::new(0., GAME_OVER_PANEL_SIZE.y / 2. - FONT_GLYPH_SIZE);
+
+/// The size of the panel shown over the play area while the game is paused.
+pub const PAUSE_PANEL_SIZE: Vec2 = Vec2::new(BRICK_FIELD_WIDTH, 48.);
+
+/// Where the pause panel is centred.
+pub const PAUSE_PANEL_POSITION: Vec2 = Vec2::new(PLAY_AREA_CENTER, 0.);
+
+/// Where the pause panel's title sits, relative to the centre of the panel.
+pub const PAUSE_TITLE_OFFSET: Vec2 = Vec2::new(0., PAUSE_PANEL_SIZE.y / 2. - FONT_GLYPH_SIZE);
+
+/// Where the left edge of the first pause menu option sits, relative to the
+/// centre of the panel.
+pub const PAUSE_OPTIONS_OFFSET: Vec2 = Vec2::new(
+    -PAUSE_PANEL_SIZE.x / 2. + FONT_GLYPH_SIZE,
+    PAUSE_TITLE_OFFSET.y - FONT_GLYPH_SIZE * 2.,
+);
+
+/// Where the logo is shown on the title screens.
+pub const LOGO_POSITION: Vec2 = Vec2::new(0., 36.);
+
+/// Where the prompt to press start is shown on the title screen.
+pub const PRESS_START_POSITION: Vec2 = Vec2::new(0., -24.);
+
+/// Where the left edge of the first option of a title screen menu sits.
+pub const TITLE_MENU_POSITION: Vec2 = Vec2::new(-56., 8.);
+
+/// Where the stage select screen's text is centred.
+pub const STAGE_SELECT_POSITION: Vec2 = Vec2::new(0., 8.);
+
+/// Where the title of the high score table is shown on the title screens.
+pub const TITLE_HIGH_SCORES_POSITION: Vec2 = Vec2::new(0., 20.);
+
+/// The fewest lives a game can be set to start with.
+pub const MIN_STARTING_LIVES: u32 = 1;
+
+/// The most lives a game can be set to start with.
+pub const MAX_STARTING_LIVES: u32 = 9;
+
+/// Where the left edge of the controls screen's title sits.
+pub const CONTROLS_POSITION: Vec2 = Vec2::new(-76., 20.);
+
+/// The most characters of a binding's name shown on the controls screen.
+pub const BINDING_NAME_LENGTH: usize = 11;
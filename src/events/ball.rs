@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Sent when the ball falls past the paddle and out of the play area.
+#[derive(Event, Default)]
+pub struct BallLost;
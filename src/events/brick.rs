@@ -0,0 +1,8 @@
+use crate::components::BrickVariant;
+use bevy::prelude::*;
+
+/// Sent when a brick runs out of hit points and is despawned.
+#[derive(Event)]
+pub struct BrickDestroyed {
+    pub variant: BrickVariant,
+}
@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 
-#[derive(Event, Default)]
-pub struct CollisionEvent;
+/// Sent when the ball collides with an entity that has a `Collider`.
+#[derive(Event)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+}
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum Collision {
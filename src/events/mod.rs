@@ -1,3 +1,7 @@
+mod ball;
+mod brick;
 mod collision;
 
+pub use ball::*;
+pub use brick::*;
 pub use collision::*;
@@ -1,16 +1,56 @@
+mod assets;
 mod components;
 mod constants;
 mod events;
 mod resources;
+mod states;
 mod systems;
 
+use assets::{Campaign, CampaignLoader, Level, LevelLoader};
 use bevy::{prelude::*, window::WindowResolution};
+use components::{
+    Ball, Brick, Countdown, DmgTone, GameOverPanel, Hud, Logo, Paddle, PausePanel, TitleText, Wall,
+};
 use constants::*;
-use events::CollisionEvent;
-use resources::Keybindings;
+use events::{BallLost, BrickDestroyed, CollisionEvent};
+use resources::{
+    ActionSources, ActionState, BallSpeed, BallSpeedConfig, BonusStage, BrickDescent, Combo,
+    ControlsOption, CurrentStage, GameOptions, HighScore, HighScores, InitialsEntry, Keybindings,
+    KeybindingsWatcher, Lives, MainMenuOption, Menu, OptionsOption, PaddleConfig, PauseOption,
+    PausedFrom, Rebinding, RespawnTimer, RestartStage, Score, ScoreConfig, StageClearTimer,
+    StageStart, StickDirection,
+};
+use states::{GameState, InGame, TitleScreen};
 use systems::{
-    fixed_update::{ball_movement, check_for_collisions, handle_input},
-    startup::{setup_camera, spawn_ball, spawn_bricks, spawn_paddle, spawn_walls},
+    cleanup::{despawn_all, remove_resource},
+    fixed_update::{
+        ball_movement, check_for_collisions, damage_bricks, descend_bricks, handle_input,
+        park_ball, score_bricks, scroll_bricks, shrink_paddle, update_ball_speed,
+    },
+    input::{
+        finish_action_frame, log_gamepad_connections, read_bindings, read_gamepad_disconnects,
+        read_mouse, read_paddle_axis, reload_keybindings, start_action_frame,
+        update_stick_directions,
+    },
+    menu::{
+        back_to_main_menu, change_option, choose_control, choose_main_menu_option, choose_option,
+        choose_pause_option, close_high_scores, navigate_menu, pause_game, pause_time, press_start,
+        rebind_action, reset_stage, select_stage, show_controls, show_high_scores, show_main_menu,
+        show_options, show_pause_menu, show_press_start, show_stage_select, spawn_title,
+        start_from_stage, unpause_time, update_pause_menu,
+    },
+    startup::{
+        apply_level_params, load_campaign, load_font, setup_camera, spawn_ball, spawn_bricks,
+        spawn_countdown, spawn_hud, spawn_paddle, spawn_walls, start_campaign,
+    },
+    update::{
+        advance_stage, award_extra_lives, check_for_ball_loss, check_for_stage_clear,
+        enter_initials, finish_bonus_stage, finish_loading, layout_bitmap_text, lose_life,
+        record_stage_start, reset_ball, reset_ball_speed, reset_combo, reset_lives, reset_score,
+        respawn_ball, restore_paddle, restore_stage_start, return_to_title, score_bonus_bricks,
+        serve_ball, show_game_over, start_stage_clear_timer, update_bonus_stage, update_high_score,
+        update_hud,
+    },
 };
 
 fn main() {
@@ -19,9 +59,23 @@ fn main() {
         ..default()
     };
 
-    let keybindings = Keybindings::load(KEYBINDINGS_PATH).unwrap_or_else(|err| {
-        warn!("Couldn't load keybindings. Falling back to defaults. {err:?}");
-        Keybindings::default()
+    let keybindings = match Keybindings::load(KEYBINDINGS_PATH, &Keybindings::default()) {
+        Ok((keybindings, warnings)) => {
+            for warning in warnings {
+                warn!("{KEYBINDINGS_PATH}:{warning}");
+            }
+
+            keybindings
+        }
+        Err(err) => {
+            warn!("Couldn't load keybindings. Falling back to defaults. {err:?}");
+            Keybindings::default()
+        }
+    };
+
+    let high_scores = HighScores::load(HIGH_SCORES_PATH).unwrap_or_else(|err| {
+        warn!("Couldn't load high scores. Falling back to defaults. {err:?}");
+        HighScores::default()
     });
 
     App::new()
@@ -30,22 +84,269 @@ fn main() {
                 .set(window_plugin)
                 .set(ImagePlugin::default_nearest()),
         )
-        .insert_resource(ClearColor(DMG_COLOR_0))
+        .insert_resource(ClearColor(DmgTone::Lightest.color()))
         .insert_resource(keybindings)
+        .insert_resource(KeybindingsWatcher::new(KEYBINDINGS_PATH))
+        .insert_resource(HighScore(high_scores.best()))
+        .insert_resource(high_scores)
+        .init_asset::<Level>()
+        .init_asset_loader::<LevelLoader>()
+        .init_asset::<Campaign>()
+        .init_asset_loader::<CampaignLoader>()
+        .init_state::<GameState>()
+        .add_computed_state::<InGame>()
+        .add_sub_state::<TitleScreen>()
+        .init_resource::<Lives>()
+        .init_resource::<Score>()
+        .init_resource::<ScoreConfig>()
+        .init_resource::<Combo>()
+        .init_resource::<CurrentStage>()
+        .init_resource::<BallSpeed>()
+        .init_resource::<BallSpeedConfig>()
+        .init_resource::<PaddleConfig>()
+        .init_resource::<ButtonInput<StickDirection>>()
+        .init_resource::<ActionState>()
+        .init_resource::<GameOptions>()
+        .init_resource::<Menu<MainMenuOption>>()
+        .init_resource::<Menu<OptionsOption>>()
+        .init_resource::<Menu<ControlsOption>>()
         .add_event::<CollisionEvent>()
+        .add_event::<BallLost>()
+        .add_event::<BrickDestroyed>()
+        .add_systems(Startup, (setup_camera, load_font, load_campaign))
+        .configure_sets(PreUpdate, ActionSources.after(bevy::input::InputSystem))
+        .add_systems(
+            PreUpdate,
+            (
+                (start_action_frame, update_stick_directions)
+                    .after(bevy::input::InputSystem)
+                    .before(ActionSources),
+                (
+                    read_bindings,
+                    read_mouse,
+                    read_paddle_axis,
+                    read_gamepad_disconnects
+                        .run_if(in_state(GameState::Serving).or(in_state(GameState::Playing))),
+                )
+                    .in_set(ActionSources),
+                finish_action_frame.after(ActionSources),
+            ),
+        )
+        .add_systems(Update, (log_gamepad_connections, reload_keybindings))
         .add_systems(
-            Startup,
+            OnEnter(InGame),
             (
-                setup_camera,
+                (reset_lives, reset_score, record_stage_start).chain(),
+                (
+                    start_campaign,
+                    apply_level_params,
+                    reset_ball_speed,
+                    spawn_bricks,
+                    spawn_countdown.run_if(resource_exists::<BonusStage>),
+                )
+                    .chain(),
                 spawn_walls,
-                spawn_bricks,
+                spawn_hud,
                 spawn_paddle,
                 spawn_ball,
             ),
         )
+        .add_systems(
+            OnExit(InGame),
+            (
+                despawn_all::<Wall>,
+                despawn_all::<Brick>,
+                despawn_all::<Paddle>,
+                despawn_all::<Ball>,
+                despawn_all::<Countdown>,
+                despawn_all::<Hud>,
+                despawn_all::<GameOverPanel>,
+                remove_resource::<RespawnTimer>,
+                remove_resource::<StageClearTimer>,
+                remove_resource::<BrickDescent>,
+                remove_resource::<BonusStage>,
+                remove_resource::<InitialsEntry>,
+                remove_resource::<StageStart>,
+            ),
+        )
+        .add_systems(
+            OnEnter(GameState::Serving),
+            (reset_ball, restore_paddle, reset_combo),
+        )
+        .add_systems(OnEnter(GameState::GameOver), show_game_over)
+        .add_systems(
+            OnEnter(GameState::StageClear),
+            (
+                reset_ball,
+                start_stage_clear_timer,
+                finish_bonus_stage.run_if(resource_exists::<BonusStage>),
+            ),
+        )
+        .add_systems(
+            OnTransition {
+                exited: GameState::StageClear,
+                entered: GameState::Serving,
+            },
+            (
+                despawn_all::<Brick>,
+                apply_level_params,
+                reset_ball_speed,
+                spawn_bricks,
+                spawn_countdown.run_if(resource_exists::<BonusStage>),
+                record_stage_start,
+            )
+                .chain(),
+        )
+        .add_systems(OnEnter(GameState::Title), spawn_title)
+        .add_systems(
+            OnExit(GameState::Title),
+            (despawn_all::<Logo>, despawn_all::<TitleText>),
+        )
+        .add_systems(OnEnter(TitleScreen::PressStart), show_press_start)
+        .add_systems(
+            OnEnter(TitleScreen::MainMenu),
+            (reset_stage, show_main_menu),
+        )
+        .add_systems(OnEnter(TitleScreen::StageSelect), show_stage_select)
+        .add_systems(OnEnter(TitleScreen::Options), show_options)
+        .add_systems(OnEnter(TitleScreen::Controls), show_controls)
+        .add_systems(OnEnter(TitleScreen::HighScores), show_high_scores)
+        .add_systems(
+            Update,
+            (
+                press_start.run_if(in_state(TitleScreen::PressStart)),
+                (
+                    navigate_menu::<MainMenuOption>,
+                    show_main_menu.run_if(resource_changed::<Menu<MainMenuOption>>),
+                    choose_main_menu_option,
+                )
+                    .chain()
+                    .run_if(in_state(TitleScreen::MainMenu)),
+                (
+                    select_stage,
+                    show_stage_select.run_if(resource_changed::<CurrentStage>),
+                    start_from_stage,
+                )
+                    .chain()
+                    .run_if(in_state(TitleScreen::StageSelect)),
+                (
+                    navigate_menu::<OptionsOption>,
+                    change_option,
+                    show_options.run_if(
+                        resource_changed::<Menu<OptionsOption>>.or(resource_changed::<GameOptions>),
+                    ),
+                    choose_option,
+                )
+                    .chain()
+                    .run_if(in_state(TitleScreen::Options)),
+                (
+                    navigate_menu::<ControlsOption>.run_if(not(resource_exists::<Rebinding>)),
+                    choose_control.run_if(not(resource_exists::<Rebinding>)),
+                    rebind_action.run_if(resource_exists::<Rebinding>),
+                    show_controls.run_if(
+                        resource_changed::<Menu<ControlsOption>>
+                            .or(resource_changed::<Keybindings>)
+                            .or(resource_exists_and_changed::<Rebinding>)
+                            .or(resource_removed::<Rebinding>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(TitleScreen::Controls)),
+                close_high_scores.run_if(in_state(TitleScreen::HighScores)),
+                back_to_main_menu.run_if(
+                    in_state(TitleScreen::StageSelect)
+                        .or(in_state(TitleScreen::Options))
+                        .or(in_state(TitleScreen::HighScores)),
+                ),
+            ),
+        )
+        .add_systems(OnEnter(GameState::Paused), (pause_time, show_pause_menu))
+        .add_systems(
+            OnExit(GameState::Paused),
+            (
+                unpause_time,
+                despawn_all::<PausePanel>,
+                remove_resource::<Menu<PauseOption>>,
+                remove_resource::<PausedFrom>,
+            ),
+        )
+        .add_systems(
+            OnTransition {
+                exited: GameState::Paused,
+                entered: GameState::Serving,
+            },
+            (
+                despawn_all::<Brick>,
+                despawn_all::<Countdown>,
+                apply_level_params,
+                reset_ball_speed,
+                spawn_bricks,
+                spawn_countdown.run_if(resource_exists::<BonusStage>),
+                restore_stage_start,
+                remove_resource::<RestartStage>,
+            )
+                .chain()
+                .run_if(resource_exists::<RestartStage>),
+        )
+        .add_systems(
+            Update,
+            (
+                finish_loading.run_if(in_state(GameState::Loading)),
+                serve_ball
+                    .run_if(in_state(GameState::Serving).and(not(resource_exists::<RespawnTimer>))),
+                respawn_ball.run_if(resource_exists::<RespawnTimer>),
+                (
+                    check_for_ball_loss,
+                    reset_ball_speed.run_if(on_event::<BallLost>),
+                    lose_life.run_if(not(resource_exists::<BonusStage>)),
+                    update_bonus_stage.run_if(resource_exists::<BonusStage>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+                check_for_stage_clear.run_if(in_state(GameState::Playing)),
+                pause_game.run_if(in_state(GameState::Serving).or(in_state(GameState::Playing))),
+                (
+                    navigate_menu::<PauseOption>,
+                    update_pause_menu.run_if(resource_changed::<Menu<PauseOption>>),
+                    choose_pause_option,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Paused)),
+                score_bonus_bricks.run_if(resource_exists::<BonusStage>),
+                (award_extra_lives, update_high_score).run_if(resource_changed::<Score>),
+                update_hud.run_if(in_state(InGame)),
+                advance_stage.run_if(resource_exists::<StageClearTimer>),
+                (
+                    return_to_title.run_if(not(resource_exists::<InitialsEntry>)),
+                    enter_initials.run_if(resource_exists::<InitialsEntry>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::GameOver)),
+            ),
+        )
         .add_systems(
             FixedUpdate,
-            (handle_input, ball_movement, check_for_collisions).chain(),
+            (
+                handle_input.run_if(in_state(GameState::Serving).or(in_state(GameState::Playing))),
+                park_ball.run_if(in_state(GameState::Serving)),
+                scroll_bricks.run_if(in_state(GameState::Serving).or(in_state(GameState::Playing))),
+                (
+                    ball_movement,
+                    check_for_collisions,
+                    damage_bricks,
+                    score_bricks.run_if(not(resource_exists::<BonusStage>)),
+                    update_ball_speed,
+                    shrink_paddle,
+                    descend_bricks.run_if(resource_exists::<BrickDescent>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+                .chain(),
+        )
+        .add_systems(
+            PostUpdate,
+            layout_bitmap_text.before(TransformSystem::TransformPropagate),
         )
         .run();
 }
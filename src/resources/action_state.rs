@@ -0,0 +1,55 @@
+use super::Action;
+use bevy::{platform::collections::HashSet, prelude::*};
+
+/// The systems which feed the player's actions into [`ActionState`] each
+/// frame. Anything that wants to drive the game, whether keys, a gamepad, a
+/// replay or an AI, adds a system to this set which holds actions down.
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ActionSources;
+
+/// The actions being performed this frame, gathered from every input source.
+/// Gameplay reads the player's intentions from here rather than from any
+/// particular device.
+#[derive(Resource, Default)]
+pub struct ActionState {
+    actions: ButtonInput<Action>,
+    held: HashSet<Action>,
+    /// Where across the screen the paddle should head for this frame, for
+    /// sources which steer it to a position rather than in a direction.
+    pub paddle_target: Option<f32>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.actions.pressed(action)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.actions.just_pressed(action)
+    }
+
+    /// Holds an action down for this frame. Called by input sources.
+    pub fn hold(&mut self, action: Action) {
+        self.held.insert(action);
+    }
+
+    /// Forgets what the input sources held last frame, ready for them to
+    /// report again.
+    pub fn start_frame(&mut self) {
+        self.held.clear();
+        self.paddle_target = None;
+    }
+
+    /// Presses the actions that were held this frame and releases the rest.
+    pub fn finish_frame(&mut self) {
+        self.actions.clear();
+
+        for action in Action::ALL {
+            if self.held.contains(&action) {
+                self.actions.press(action);
+            } else {
+                self.actions.release(action);
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::components::BrickVariant;
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The events which push the ball up to a faster speed tier. Each trigger
+/// raises the ball to *at least* the given tier, so the ball never slows down
+/// until it is lost.
+#[derive(Resource)]
+pub struct BallSpeedConfig {
+    /// The speed of the ball at each tier, slowest first.
+    pub tiers: Vec<f32>,
+    /// Pairs of paddle hit counts and the tier reached once the ball has been
+    /// returned that many times.
+    pub paddle_hits: Vec<(u32, usize)>,
+    /// The tier reached once the ball touches the top wall.
+    pub top_wall: Option<usize>,
+    /// Pairs of brick variants and the tier reached once one is destroyed.
+    pub bricks: Vec<(BrickVariant, usize)>,
+}
+
+impl Default for BallSpeedConfig {
+    fn default() -> Self {
+        Self {
+            tiers: BALL_SPEED_TIERS.to_vec(),
+            paddle_hits: vec![(4, 1), (12, 2)],
+            top_wall: Some(3),
+            bricks: vec![(BrickVariant::MultiHit, 2)],
+        }
+    }
+}
+
+/// The current speed tier of the ball, and the progress towards the next.
+#[derive(Resource, Default)]
+pub struct BallSpeed {
+    pub tier: usize,
+    pub paddle_hits: u32,
+}
+
+impl BallSpeed {
+    pub fn speed(&self, config: &BallSpeedConfig) -> f32 {
+        config
+            .tiers
+            .get(self.tier)
+            .or(config.tiers.last())
+            .copied()
+            .unwrap_or(BALL_SPEED_TIERS[0])
+    }
+
+    /// Raises the ball to at least `tier`, returning whether it sped up.
+    pub fn raise_to(&mut self, tier: usize, config: &BallSpeedConfig) -> bool {
+        let tier = tier.min(config.tiers.len().saturating_sub(1));
+
+        if tier > self.tier {
+            self.tier = tier;
+            return true;
+        }
+
+        false
+    }
+}
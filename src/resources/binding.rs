@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+use serde::de::{DeserializeOwned, IntoDeserializer, value};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The prefix marking a binding in the keybindings file as a gamepad input
+/// rather than a key.
+const GAMEPAD_PREFIX: &str = "Gamepad:";
+
+/// One direction of a gamepad axis, which is pressed while the axis is pushed
+/// past the deadzone that way.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct StickDirection {
+    pub axis: GamepadAxis,
+    pub positive: bool,
+}
+
+impl StickDirection {
+    /// Both directions of every gamepad axis.
+    pub fn all() -> impl Iterator<Item = StickDirection> {
+        [
+            GamepadAxis::LeftStickX,
+            GamepadAxis::LeftStickY,
+            GamepadAxis::LeftZ,
+            GamepadAxis::RightStickX,
+            GamepadAxis::RightStickY,
+            GamepadAxis::RightZ,
+        ]
+        .into_iter()
+        .flat_map(|axis| [false, true].map(|positive| StickDirection { axis, positive }))
+    }
+
+    /// Whether an axis value pushes past the deadzone in this direction.
+    pub fn is_pushed(&self, value: f32, deadzone: f32) -> bool {
+        if self.positive {
+            value > deadzone
+        } else {
+            value < -deadzone
+        }
+    }
+}
+
+/// An input which can be bound to an action: a key, a gamepad button, or one
+/// direction of a gamepad axis.
+///
+/// Keys are written as their `KeyCode`, such as `"ArrowLeft"`. Gamepad inputs
+/// are prefixed, such as `"Gamepad:DPadLeft"`, with a trailing `+` or `-` for
+/// the direction of an axis, such as `"Gamepad:LeftStickX-"`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Binding {
+    Key(KeyCode),
+    GamepadButton(GamepadButton),
+    GamepadAxis(StickDirection),
+}
+
+impl Binding {
+    pub fn is_gamepad(&self) -> bool {
+        !matches!(self, Self::Key(_))
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key:?}"),
+            Self::GamepadButton(button) => write!(f, "{GAMEPAD_PREFIX}{button:?}"),
+            Self::GamepadAxis(direction) => {
+                let sign = if direction.positive { '+' } else { '-' };
+                write!(f, "{GAMEPAD_PREFIX}{:?}{sign}", direction.axis)
+            }
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = value::Error;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        let Some(name) = name.strip_prefix(GAMEPAD_PREFIX) else {
+            return parse_variant(name).map(Self::Key);
+        };
+
+        if let Some(axis) = name.strip_suffix('+') {
+            return parse_variant(axis).map(|axis| {
+                Self::GamepadAxis(StickDirection {
+                    axis,
+                    positive: true,
+                })
+            });
+        }
+
+        if let Some(axis) = name.strip_suffix('-') {
+            return parse_variant(axis).map(|axis| {
+                Self::GamepadAxis(StickDirection {
+                    axis,
+                    positive: false,
+                })
+            });
+        }
+
+        parse_variant(name).map(Self::GamepadButton)
+    }
+}
+
+/// Parses the name of a unit variant of one of Bevy's input enums.
+fn parse_variant<T: DeserializeOwned>(name: &str) -> Result<T, value::Error> {
+    T::deserialize(name.into_deserializer())
+}
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindable_inputs_round_trip_through_their_names() {
+        let keys = [
+            KeyCode::KeyA,
+            KeyCode::Digit1,
+            KeyCode::Space,
+            KeyCode::Enter,
+            KeyCode::Escape,
+            KeyCode::ArrowLeft,
+            KeyCode::ShiftLeft,
+            KeyCode::NumpadEnter,
+            KeyCode::F12,
+        ]
+        .map(Binding::Key);
+        let buttons = [
+            GamepadButton::South,
+            GamepadButton::East,
+            GamepadButton::North,
+            GamepadButton::West,
+            GamepadButton::C,
+            GamepadButton::Z,
+            GamepadButton::LeftTrigger,
+            GamepadButton::LeftTrigger2,
+            GamepadButton::RightTrigger,
+            GamepadButton::RightTrigger2,
+            GamepadButton::Select,
+            GamepadButton::Start,
+            GamepadButton::Mode,
+            GamepadButton::LeftThumb,
+            GamepadButton::RightThumb,
+            GamepadButton::DPadUp,
+            GamepadButton::DPadDown,
+            GamepadButton::DPadLeft,
+            GamepadButton::DPadRight,
+        ]
+        .map(Binding::GamepadButton);
+        let sticks = StickDirection::all().map(Binding::GamepadAxis);
+
+        for binding in keys.into_iter().chain(buttons).chain(sticks) {
+            assert_eq!(binding.to_string().parse::<Binding>().ok(), Some(binding));
+        }
+    }
+
+    #[test]
+    fn names_are_prefixed_for_gamepad_inputs() {
+        let direction = StickDirection {
+            axis: GamepadAxis::LeftStickX,
+            positive: false,
+        };
+
+        assert_eq!(Binding::Key(KeyCode::ArrowLeft).to_string(), "ArrowLeft");
+        assert_eq!(
+            Binding::GamepadButton(GamepadButton::DPadLeft).to_string(),
+            "Gamepad:DPadLeft"
+        );
+        assert_eq!(
+            Binding::GamepadAxis(direction).to_string(),
+            "Gamepad:LeftStickX-"
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The sprite sheet of the 8x8 bitmap font, and the atlas layout splitting it
+/// into glyphs.
+#[derive(Resource)]
+pub struct BitmapFont {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl BitmapFont {
+    /// The atlas index of the glyph for a character. The font only has upper
+    /// case letters, which lower case letters share, and any other character
+    /// it doesn't have is drawn as a question mark.
+    pub fn glyph_index(character: char) -> usize {
+        let character = character.to_ascii_uppercase();
+        let first = FONT_FIRST_CHARACTER as usize;
+        let glyph_count = (FONT_COLUMNS * FONT_ROWS) as usize;
+
+        match (character as usize).checked_sub(first) {
+            Some(index) if index < glyph_count => index,
+            _ => '?' as usize - first,
+        }
+    }
+}
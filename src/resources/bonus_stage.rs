@@ -0,0 +1,26 @@
+use crate::assets::BonusParams;
+use bevy::prelude::*;
+
+/// Present while a bonus stage is being played, counting down the time the
+/// player has left to clear it.
+#[derive(Resource)]
+pub struct BonusStage {
+    pub timer: Timer,
+    pub brick_score: u32,
+    pub perfect_score: u32,
+}
+
+impl BonusStage {
+    pub fn new(params: &BonusParams) -> Self {
+        Self {
+            timer: Timer::from_seconds(params.time_limit, TimerMode::Once),
+            brick_score: params.brick_score,
+            perfect_score: params.perfect_score,
+        }
+    }
+
+    /// The whole seconds left on the clock, rounded up.
+    pub fn seconds_remaining(&self) -> u32 {
+        self.timer.remaining_secs().ceil() as u32
+    }
+}
@@ -0,0 +1,32 @@
+use crate::assets::DescendParams;
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Present on levels where the brick field creeps down towards the paddle,
+/// tracking how far it has descended.
+#[derive(Resource)]
+pub struct BrickDescent {
+    /// The number of rows the field has stepped down since it was spawned.
+    pub rows: usize,
+    pub timer: Option<Timer>,
+    pub paddle_hits: Option<u32>,
+    pub hits_since_step: u32,
+}
+
+impl BrickDescent {
+    pub fn new(params: &DescendParams) -> Self {
+        Self {
+            rows: 0,
+            timer: params
+                .interval
+                .map(|interval| Timer::from_seconds(interval, TimerMode::Repeating)),
+            paddle_hits: params.paddle_hits,
+            hits_since_step: 0,
+        }
+    }
+
+    /// The vertical distance the field has descended.
+    pub fn offset(&self) -> f32 {
+        self.rows as f32 * BRICK_SIZE.y
+    }
+}
@@ -0,0 +1,6 @@
+use crate::assets::Level;
+use bevy::prelude::*;
+
+/// The level that is spawned when the play field is set up.
+#[derive(Resource, Deref)]
+pub struct CurrentLevel(pub Handle<Level>);
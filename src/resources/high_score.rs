@@ -0,0 +1,205 @@
+use crate::components::BitmapText;
+use crate::constants::*;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The best score reached so far.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct HighScore(pub u32);
+
+/// A single entry in the high score table.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HighScoreEntry {
+    pub initials: String,
+    pub score: u32,
+    /// The number of the stage the game ended on, starting from 1.
+    pub stage: usize,
+    /// The day the score was set, as `YYYY-MM-DD`.
+    pub date: String,
+}
+
+impl HighScoreEntry {
+    /// Creates an entry dated today.
+    pub fn new(initials: String, score: u32, stage: usize) -> Self {
+        Self {
+            initials,
+            score,
+            stage,
+            date: today(),
+        }
+    }
+}
+
+/// The best scores set so far, highest first, which are kept between runs.
+#[derive(Resource, Serialize, Deserialize, Default)]
+pub struct HighScores {
+    #[serde(default)]
+    pub entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let mut high_scores: Self = toml::from_str(&content)?;
+
+        high_scores
+            .entries
+            .sort_by_key(|entry| Reverse(entry.score));
+        high_scores.entries.truncate(HIGH_SCORE_COUNT);
+
+        Ok(high_scores)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        std::fs::write(path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The highest score in the table, or 0 if it is empty.
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.score)
+    }
+
+    /// Whether a score is high enough to earn a place in the table.
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && (self.entries.len() < HIGH_SCORE_COUNT
+                || self.entries.iter().any(|entry| score > entry.score))
+    }
+
+    /// Lists the table beneath a title, one entry per line.
+    pub fn table(&self, title: &str) -> String {
+        let mut text = format!("{title}\n");
+
+        for (rank, entry) in self.entries.iter().enumerate() {
+            let score = BitmapText::number(entry.score, HUD_SCORE_DIGITS);
+            text += &format!("\n{:>2} {:<3.3} {score}", rank + 1, entry.initials);
+        }
+
+        text
+    }
+
+    /// Adds an entry below any with an equal or higher score, dropping the
+    /// lowest entry if the table is full.
+    pub fn insert(&mut self, entry: HighScoreEntry) {
+        let position = self
+            .entries
+            .iter()
+            .position(|existing| entry.score > existing.score)
+            .unwrap_or(self.entries.len());
+
+        self.entries.insert(position, entry);
+        self.entries.truncate(HIGH_SCORE_COUNT);
+    }
+}
+
+/// Today's date in UTC as `YYYY-MM-DD`.
+fn today() -> String {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+
+    civil_date((seconds / 86_400) as i64)
+}
+
+/// Converts days since the Unix epoch to a civil date as `YYYY-MM-DD`, as
+/// described in http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_date(days_since_epoch: i64) -> String {
+    let days = days_since_epoch + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(initials: &str, score: u32) -> HighScoreEntry {
+        HighScoreEntry {
+            initials: String::from(initials),
+            score,
+            stage: 1,
+            date: String::from("2024-01-01"),
+        }
+    }
+
+    fn full_table() -> HighScores {
+        HighScores {
+            entries: (1..=HIGH_SCORE_COUNT as u32)
+                .rev()
+                .map(|rank| entry("AAA", rank * 100))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn any_positive_score_qualifies_for_a_table_with_room() {
+        let high_scores = HighScores {
+            entries: vec![entry("AAA", 500)],
+        };
+
+        assert!(high_scores.qualifies(1));
+        assert!(!high_scores.qualifies(0));
+    }
+
+    #[test]
+    fn score_must_beat_an_entry_of_a_full_table() {
+        let high_scores = full_table();
+
+        assert!(high_scores.qualifies(101));
+        assert!(!high_scores.qualifies(100));
+    }
+
+    #[test]
+    fn insert_places_ties_below_existing_entries() {
+        let mut high_scores = HighScores {
+            entries: vec![entry("AAA", 300), entry("BBB", 200), entry("CCC", 100)],
+        };
+
+        high_scores.insert(entry("NEW", 200));
+
+        let initials: Vec<_> = high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.initials.as_str())
+            .collect();
+        assert_eq!(initials, ["AAA", "BBB", "NEW", "CCC"]);
+    }
+
+    #[test]
+    fn insert_into_a_full_table_drops_the_lowest_entry() {
+        let mut high_scores = full_table();
+
+        high_scores.insert(entry("NEW", 550));
+
+        assert_eq!(high_scores.entries.len(), HIGH_SCORE_COUNT);
+        assert_eq!(high_scores.entries[5].initials, "NEW");
+        assert_eq!(high_scores.entries.last().unwrap().score, 200);
+    }
+
+    #[test]
+    fn days_since_epoch_are_converted_to_civil_dates() {
+        assert_eq!(civil_date(0), "1970-01-01");
+        assert_eq!(civil_date(10_956), "1999-12-31");
+        assert_eq!(civil_date(11_016), "2000-02-29");
+        assert_eq!(civil_date(19_782), "2024-02-29");
+        assert_eq!(civil_date(19_783), "2024-03-01");
+    }
+}
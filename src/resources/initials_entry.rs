@@ -0,0 +1,50 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// The initials being entered for a new high score, one letter at a time.
+#[derive(Resource)]
+pub struct InitialsEntry {
+    /// The letters confirmed so far.
+    pub initials: String,
+    /// The letter currently being chosen.
+    pub letter: char,
+}
+
+impl Default for InitialsEntry {
+    fn default() -> Self {
+        Self {
+            initials: String::new(),
+            letter: 'A',
+        }
+    }
+}
+
+impl InitialsEntry {
+    pub fn previous_letter(&mut self) {
+        self.letter = match self.letter {
+            'A' => 'Z',
+            letter => (letter as u8 - 1) as char,
+        };
+    }
+
+    pub fn next_letter(&mut self) {
+        self.letter = match self.letter {
+            'Z' => 'A',
+            letter => (letter as u8 + 1) as char,
+        };
+    }
+
+    /// Confirms the current letter, returning whether all of the initials have
+    /// now been entered.
+    pub fn confirm(&mut self) -> bool {
+        self.initials.push(self.letter);
+        self.initials.len() == INITIALS_LENGTH
+    }
+
+    /// The initials as shown while they are entered, with the letter being
+    /// chosen after those already confirmed.
+    pub fn text(&self) -> String {
+        let entered = format!("{}{}", self.initials, self.letter);
+        format!("{entered:.<INITIALS_LENGTH$}")
+    }
+}
@@ -1,28 +1,161 @@
+use super::{Binding, KeybindingsWarning};
+use crate::constants::*;
 use bevy::prelude::*;
-use serde::Deserialize;
 use std::path::Path;
 
-#[derive(Resource, Deserialize)]
+/// An action the player can bind keys to.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    MoveUp,
+    MoveDown,
+    Serve,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Self::MoveLeft,
+        Self::MoveRight,
+        Self::MoveUp,
+        Self::MoveDown,
+        Self::Serve,
+        Self::Pause,
+    ];
+
+    /// The name of the action in the keybindings file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MoveLeft => "move_left",
+            Self::MoveRight => "move_right",
+            Self::MoveUp => "move_up",
+            Self::MoveDown => "move_down",
+            Self::Serve => "serve",
+            Self::Pause => "pause",
+        }
+    }
+
+    /// The action with the given name in the keybindings file.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    /// The name of the action as shown in menus.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::MoveLeft => "LEFT",
+            Self::MoveRight => "RIGHT",
+            Self::MoveUp => "UP",
+            Self::MoveDown => "DOWN",
+            Self::Serve => "SERVE",
+            Self::Pause => "PAUSE",
+        }
+    }
+}
+
+/// The inputs bound to each action, along with the settings for analog
+/// input. Anything left out of the keybindings file keeps its default.
+#[derive(Resource, Clone)]
 pub struct Keybindings {
-    pub move_left: Vec<KeyCode>,
-    pub move_right: Vec<KeyCode>,
-    pub serve: Vec<KeyCode>,
+    pub move_left: Vec<Binding>,
+    pub move_right: Vec<Binding>,
+    pub move_up: Vec<Binding>,
+    pub move_down: Vec<Binding>,
+    pub serve: Vec<Binding>,
+    pub pause: Vec<Binding>,
+    /// How far a gamepad axis must be pushed before it counts as pressed.
+    pub deadzone: f32,
 }
 
 impl Default for Keybindings {
     fn default() -> Self {
         Self {
-            move_left: vec![KeyCode::ArrowLeft],
-            move_right: vec![KeyCode::ArrowRight],
-            serve: vec![KeyCode::Space],
+            move_left: vec![
+                Binding::Key(KeyCode::ArrowLeft),
+                Binding::GamepadButton(GamepadButton::DPadLeft),
+            ],
+            move_right: vec![
+                Binding::Key(KeyCode::ArrowRight),
+                Binding::GamepadButton(GamepadButton::DPadRight),
+            ],
+            move_up: vec![
+                Binding::Key(KeyCode::ArrowUp),
+                Binding::GamepadButton(GamepadButton::DPadUp),
+            ],
+            move_down: vec![
+                Binding::Key(KeyCode::ArrowDown),
+                Binding::GamepadButton(GamepadButton::DPadDown),
+            ],
+            serve: vec![
+                Binding::Key(KeyCode::Space),
+                Binding::Key(KeyCode::Enter),
+                Binding::GamepadButton(GamepadButton::South),
+            ],
+            pause: vec![
+                Binding::Key(KeyCode::Escape),
+                Binding::GamepadButton(GamepadButton::Start),
+            ],
+            deadzone: GAMEPAD_DEADZONE,
         }
     }
 }
 
 impl Keybindings {
-    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
-        let path = path.as_ref();
+    /// Loads the keybindings from a file over the top of `base`, along with
+    /// any warnings about parts of it which were skipped.
+    pub fn load(
+        path: impl AsRef<Path>,
+        base: &Self,
+    ) -> anyhow::Result<(Self, Vec<KeybindingsWarning>)> {
         let content = std::fs::read_to_string(path)?;
-        Ok(toml::from_str(&content)?)
+        Self::parse(&content, base)
+    }
+
+    /// Writes the keybindings back out in the same `"action" = ["KeyCode"]`
+    /// format that they are loaded from.
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let mut content = String::new();
+
+        for action in Action::ALL {
+            let bindings = toml::Value::try_from(self.bindings(action))?;
+            content += &format!("\"{}\" = {bindings}\n", action.name());
+        }
+
+        // Written from the `f32` itself, as widening it to a TOML float would
+        // write out its rounding error, e.g. 0.30000001192092896 for 0.3.
+        content += &format!("\"deadzone\" = {:?}\n", self.deadzone);
+
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+
+    pub fn bindings(&self, action: Action) -> &Vec<Binding> {
+        match action {
+            Action::MoveLeft => &self.move_left,
+            Action::MoveRight => &self.move_right,
+            Action::MoveUp => &self.move_up,
+            Action::MoveDown => &self.move_down,
+            Action::Serve => &self.serve,
+            Action::Pause => &self.pause,
+        }
+    }
+
+    pub fn bindings_mut(&mut self, action: Action) -> &mut Vec<Binding> {
+        match action {
+            Action::MoveLeft => &mut self.move_left,
+            Action::MoveRight => &mut self.move_right,
+            Action::MoveUp => &mut self.move_up,
+            Action::MoveDown => &mut self.move_down,
+            Action::Serve => &mut self.serve,
+            Action::Pause => &mut self.pause,
+        }
+    }
+
+    /// The action an input is bound to, if any.
+    pub fn action_for(&self, binding: Binding) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|&action| self.bindings(action).contains(&binding))
     }
 }
@@ -0,0 +1,410 @@
+use super::{Action, Binding, Keybindings};
+use serde::de::{self, Deserialize, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use toml::Spanned;
+
+/// A problem with one part of the keybindings file, which is skipped in
+/// favour of the default while the rest of the file still loads.
+pub struct KeybindingsWarning {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl KeybindingsWarning {
+    fn new(content: &str, span: Range<usize>, message: String) -> Self {
+        let before = &content[..span.start];
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count()
+                + 1,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for KeybindingsWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// The value of a setting in the keybindings file, before it is checked
+/// against the setting it belongs to.
+enum Setting {
+    Bindings(Vec<Spanned<Input>>),
+    Number(f64),
+    /// Any other kind of value, which no setting takes.
+    Other,
+}
+
+/// One element of a list of inputs, before its name is parsed.
+enum Input {
+    Name(String),
+    /// Any value other than a string, which can't name an input.
+    Other,
+}
+
+impl<'de> Deserialize<'de> for Input {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InputVisitor;
+
+        impl<'de> Visitor<'de> for InputVisitor {
+            type Value = Input;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("the name of an input")
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> Result<Input, E> {
+                Ok(Input::Name(name.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, _: i64) -> Result<Input, E> {
+                Ok(Input::Other)
+            }
+
+            fn visit_f64<E: de::Error>(self, _: f64) -> Result<Input, E> {
+                Ok(Input::Other)
+            }
+
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Input, E> {
+                Ok(Input::Other)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Input, A::Error> {
+                IgnoredAny.visit_seq(seq)?;
+                Ok(Input::Other)
+            }
+
+            // Inline tables, and datetimes, which TOML hands over as a table.
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Input, A::Error> {
+                IgnoredAny.visit_map(map)?;
+                Ok(Input::Other)
+            }
+        }
+
+        deserializer.deserialize_any(InputVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Setting {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SettingVisitor;
+
+        impl<'de> Visitor<'de> for SettingVisitor {
+            type Value = Setting;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of inputs or a number")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Setting, A::Error> {
+                let mut names = Vec::new();
+
+                while let Some(name) = seq.next_element()? {
+                    names.push(name);
+                }
+
+                Ok(Setting::Bindings(names))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Setting, E> {
+                Ok(Setting::Number(value))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Setting, E> {
+                Ok(Setting::Number(value as f64))
+            }
+
+            fn visit_str<E: de::Error>(self, _: &str) -> Result<Setting, E> {
+                Ok(Setting::Other)
+            }
+
+            fn visit_bool<E: de::Error>(self, _: bool) -> Result<Setting, E> {
+                Ok(Setting::Other)
+            }
+
+            // Tables, and datetimes, which TOML hands over as a table.
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Setting, A::Error> {
+                IgnoredAny.visit_map(map)?;
+                Ok(Setting::Other)
+            }
+        }
+
+        deserializer.deserialize_any(SettingVisitor)
+    }
+}
+
+impl Keybindings {
+    /// Parses a keybindings file over the top of `base`, which keeps any
+    /// setting the file leaves out or gets wrong. Only a file which isn't
+    /// valid TOML fails to parse; every other problem, including a setting or
+    /// input of the wrong type, is returned as a warning.
+    pub fn parse(content: &str, base: &Self) -> anyhow::Result<(Self, Vec<KeybindingsWarning>)> {
+        let mut settings: Vec<(Spanned<String>, Spanned<Setting>)> =
+            toml::from_str::<BTreeMap<_, _>>(content)?
+                .into_iter()
+                .collect();
+        settings.sort_by_key(|(key, _)| key.span().start);
+
+        let mut keybindings = base.clone();
+        let mut warnings = Vec::new();
+        let mut warn = |span: Range<usize>, message: String| {
+            warnings.push(KeybindingsWarning::new(content, span, message));
+        };
+        // Every binding taken from the file, with where it was written.
+        let mut bound: Vec<(Action, Binding, Range<usize>)> = Vec::new();
+        let mut rebound = Vec::new();
+
+        for (key, setting) in settings {
+            let name = key.get_ref().as_str();
+            let span = setting.span();
+
+            match (name, setting.into_inner()) {
+                ("deadzone", Setting::Number(deadzone)) if (0. ..1.).contains(&deadzone) => {
+                    keybindings.deadzone = deadzone as f32;
+                }
+                ("deadzone", _) => warn(
+                    span,
+                    "`deadzone` should be a number from 0 up to 1".to_string(),
+                ),
+                (name, setting) => {
+                    let Some(action) = Action::from_name(name) else {
+                        warn(key.span(), format!("unknown action `{name}`"));
+                        continue;
+                    };
+
+                    let Setting::Bindings(names) = setting else {
+                        warn(span, format!("`{name}` should be a list of inputs"));
+                        continue;
+                    };
+
+                    let mut bindings = Vec::new();
+
+                    for input in names {
+                        let span = input.span();
+
+                        let Input::Name(input) = input.into_inner() else {
+                            warn(span, "inputs should be names in quotes".to_string());
+                            continue;
+                        };
+
+                        match input.parse::<Binding>() {
+                            Ok(binding) => {
+                                bindings.push(binding);
+                                bound.push((action, binding, span));
+                            }
+                            Err(_) => warn(span, format!("unknown input `{input}`")),
+                        }
+                    }
+
+                    if bindings.is_empty() {
+                        warn(
+                            span,
+                            format!(
+                                "`{name}` has nothing usable bound to it, so is left as it was"
+                            ),
+                        );
+                    } else {
+                        *keybindings.bindings_mut(action) = bindings;
+                        rebound.push(action);
+                    }
+                }
+            }
+        }
+
+        // An action the file leaves alone keeps its inputs from `base`, apart
+        // from any the file has bound to another action.
+        for action in Action::ALL {
+            if rebound.contains(&action) {
+                continue;
+            }
+
+            // Where the file took the last of the action's inputs, if it did.
+            let mut taken = None;
+
+            keybindings.bindings_mut(action).retain(|binding| {
+                match bound
+                    .iter()
+                    .find(|(other, other_binding, _)| *other != action && other_binding == binding)
+                {
+                    Some((_, _, span)) => {
+                        taken = Some(span.clone());
+                        false
+                    }
+                    None => true,
+                }
+            });
+
+            if let Some(span) = taken.filter(|_| keybindings.bindings(action).is_empty()) {
+                warn(
+                    span,
+                    format!("this leaves `{}` with nothing bound to it", action.name()),
+                );
+            }
+        }
+
+        // An input bound to two actions would perform both at once, which the
+        // controls screen refuses as well. It is flagged where it is bound the
+        // second time.
+        for (index, (action, binding, span)) in bound.iter().enumerate() {
+            let first = bound[..index]
+                .iter()
+                .find(|(other, other_binding, _)| other != action && other_binding == binding);
+
+            if let Some((other, _, _)) = first {
+                warn(
+                    span.clone(),
+                    format!(
+                        "`{binding}` is bound to both `{}` and `{}`",
+                        other.name(),
+                        action.name()
+                    ),
+                );
+            }
+        }
+
+        Ok((keybindings, warnings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::prelude::*;
+
+    /// Parses over the default keybindings, with the warnings as they are
+    /// logged.
+    fn parse(content: &str) -> (Keybindings, Vec<String>) {
+        let (keybindings, warnings) = Keybindings::parse(content, &Keybindings::default())
+            .expect("the file should be valid TOML");
+
+        (
+            keybindings,
+            warnings.iter().map(ToString::to_string).collect(),
+        )
+    }
+
+    #[test]
+    fn partial_file_is_merged_with_defaults() {
+        let (keybindings, warnings) = parse(r#""serve" = ["KeyX"]"#);
+        let defaults = Keybindings::default();
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(keybindings.serve, vec![Binding::Key(KeyCode::KeyX)]);
+        assert_eq!(keybindings.move_left, defaults.move_left);
+        assert_eq!(keybindings.pause, defaults.pause);
+        assert_eq!(keybindings.deadzone, defaults.deadzone);
+    }
+
+    #[test]
+    fn unknown_action_is_reported() {
+        let (_, warnings) = parse("\"serve\" = [\"Space\"]\njump = [\"KeyJ\"]");
+
+        assert_eq!(warnings, ["2:1: unknown action `jump`"]);
+    }
+
+    #[test]
+    fn unknown_input_is_reported_and_skipped() {
+        let (keybindings, warnings) = parse(r#""serve" = ["Space", "Nope"]"#);
+
+        assert_eq!(warnings, ["1:21: unknown input `Nope`"]);
+        assert_eq!(keybindings.serve, vec![Binding::Key(KeyCode::Space)]);
+    }
+
+    #[test]
+    fn input_of_the_wrong_type_is_reported_and_skipped() {
+        let (keybindings, warnings) = parse(r#""serve" = ["Space", 5]"#);
+
+        assert_eq!(warnings, ["1:21: inputs should be names in quotes"]);
+        assert_eq!(keybindings.serve, vec![Binding::Key(KeyCode::Space)]);
+    }
+
+    #[test]
+    fn tables_and_datetimes_are_reported() {
+        let content = "\"serve\" = [{ key = \"Space\" }, 1979-05-27]\n\"pause\" = 1979-05-27\n\"deadzone\" = {}";
+        let (keybindings, warnings) = parse(content);
+
+        assert_eq!(
+            warnings,
+            [
+                "1:12: inputs should be names in quotes",
+                "1:31: inputs should be names in quotes",
+                "1:11: `serve` has nothing usable bound to it, so is left as it was",
+                "2:11: `pause` should be a list of inputs",
+                "3:14: `deadzone` should be a number from 0 up to 1",
+            ]
+        );
+        assert_eq!(keybindings.serve, Keybindings::default().serve);
+    }
+
+    #[test]
+    fn warning_positions_count_lines_and_characters() {
+        let content = "# Bindings\n\n  \"pause\" = [\"É\", \"Nope\"]\n";
+        let (keybindings, warnings) = parse(content);
+
+        assert_eq!(
+            warnings,
+            [
+                "3:14: unknown input `É`",
+                "3:19: unknown input `Nope`",
+                "3:13: `pause` has nothing usable bound to it, so is left as it was",
+            ]
+        );
+        assert_eq!(keybindings.pause, Keybindings::default().pause);
+    }
+
+    #[test]
+    fn input_bound_to_left_and_right_is_reported() {
+        let (_, warnings) = parse("\"move_left\" = [\"KeyA\"]\n\"move_right\" = [\"KeyA\"]");
+
+        assert_eq!(
+            warnings,
+            ["2:17: `KeyA` is bound to both `move_left` and `move_right`"]
+        );
+    }
+
+    #[test]
+    fn defaults_skip_inputs_the_file_binds_elsewhere() {
+        let (keybindings, warnings) = parse(r#""serve" = ["Space", "KeyW", "ArrowUp"]"#);
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert!(
+            !keybindings
+                .move_up
+                .contains(&Binding::Key(KeyCode::ArrowUp))
+        );
+        assert_eq!(
+            keybindings.action_for(Binding::Key(KeyCode::ArrowUp)),
+            Some(Action::Serve)
+        );
+    }
+
+    #[test]
+    fn mistakes_keep_the_bindings_being_merged_over() {
+        let current = Keybindings {
+            serve: vec![Binding::Key(KeyCode::KeyX)],
+            ..default()
+        };
+
+        let (keybindings, warnings) =
+            Keybindings::parse(r#""serve" = ["Spcae"]"#, &current).unwrap();
+
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(keybindings.serve, current.serve);
+    }
+
+    #[test]
+    fn invalid_toml_fails_to_parse() {
+        assert!(Keybindings::parse("\"serve\" = [", &Keybindings::default()).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::constants::*;
+use bevy::prelude::*;
+use std::{path::Path, time::SystemTime};
+
+/// Watches the keybindings file for changes by polling when it was last
+/// modified.
+#[derive(Resource)]
+pub struct KeybindingsWatcher {
+    pub timer: Timer,
+    /// When the file was last modified as of the last check, or `None` if it
+    /// couldn't be read.
+    pub modified: Option<SystemTime>,
+}
+
+impl KeybindingsWatcher {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            timer: Timer::from_seconds(KEYBINDINGS_POLL_INTERVAL, TimerMode::Repeating),
+            modified: Self::modified(path),
+        }
+    }
+
+    pub fn modified(path: impl AsRef<Path>) -> Option<SystemTime> {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Records when the file was last modified, returning whether that has
+    /// changed since the last check.
+    pub fn check(&mut self, path: impl AsRef<Path>) -> bool {
+        let modified = Self::modified(path);
+        let changed = modified.is_some() && modified != self.modified;
+        self.modified = modified;
+        changed
+    }
+}
@@ -0,0 +1,11 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct Lives(pub u32);
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self(STARTING_LIVES)
+    }
+}
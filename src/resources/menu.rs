@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// An option which can be listed in a menu.
+pub trait MenuOption: Copy + PartialEq + Send + Sync + 'static {
+    /// Every option in the menu, in the order they are listed.
+    const ALL: &'static [Self];
+
+    fn label(&self) -> &str;
+}
+
+/// A menu of options, one of which is selected. The selection wraps around
+/// at either end of the menu.
+#[derive(Resource)]
+pub struct Menu<T: MenuOption> {
+    selected: usize,
+    options: PhantomData<T>,
+}
+
+impl<T: MenuOption> Default for Menu<T> {
+    fn default() -> Self {
+        Self {
+            selected: 0,
+            options: PhantomData,
+        }
+    }
+}
+
+impl<T: MenuOption> Menu<T> {
+    pub fn option(&self) -> T {
+        T::ALL[self.selected]
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = (self.selected + T::ALL.len() - 1) % T::ALL.len();
+    }
+
+    pub fn next(&mut self) {
+        self.selected = (self.selected + 1) % T::ALL.len();
+    }
+
+    /// Lists the options, with a cursor beside the selected one.
+    pub fn text(&self) -> String {
+        self.text_with(|option| option.label().to_string())
+    }
+
+    /// Lists the options with labels of their own, for options which show a
+    /// value alongside their label.
+    pub fn text_with(&self, label: impl Fn(T) -> String) -> String {
+        T::ALL
+            .iter()
+            .map(|&option| {
+                let cursor = if option == self.option() { '>' } else { ' ' };
+                format!("{cursor} {}", label(option))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
@@ -1,3 +1,43 @@
+mod action_state;
+mod ball_speed;
+mod binding;
+mod bitmap_font;
+mod bonus_stage;
+mod brick_descent;
+mod current_level;
+mod high_score;
+mod initials_entry;
 mod keybindings;
+mod keybindings_file;
+mod keybindings_watcher;
+mod lives;
+mod menu;
+mod paddle_config;
+mod pause_menu;
+mod respawn_timer;
+mod score;
+mod stage;
+mod stage_clear_timer;
+mod title_menu;
 
+pub use action_state::*;
+pub use ball_speed::*;
+pub use binding::*;
+pub use bitmap_font::*;
+pub use bonus_stage::*;
+pub use brick_descent::*;
+pub use current_level::*;
+pub use high_score::*;
+pub use initials_entry::*;
 pub use keybindings::*;
+pub use keybindings_file::*;
+pub use keybindings_watcher::*;
+pub use lives::*;
+pub use menu::*;
+pub use paddle_config::*;
+pub use pause_menu::*;
+pub use respawn_timer::*;
+pub use score::*;
+pub use stage::*;
+pub use stage_clear_timer::*;
+pub use title_menu::*;
@@ -0,0 +1,21 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// How the paddle moves when it is steered to a position, by the mouse or an
+/// analog stick, rather than in a direction.
+#[derive(Resource)]
+pub struct PaddleConfig {
+    /// The fastest the paddle may chase the position it is steered to.
+    pub max_speed: f32,
+    /// The gamepad axis whose position the paddle follows.
+    pub analog_axis: GamepadAxis,
+}
+
+impl Default for PaddleConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: PADDLE_MAX_SPEED,
+            analog_axis: PADDLE_ANALOG_AXIS,
+        }
+    }
+}
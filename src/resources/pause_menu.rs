@@ -0,0 +1,40 @@
+use super::MenuOption;
+use crate::states::GameState;
+use bevy::prelude::*;
+
+/// An option in the pause menu.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PauseOption {
+    Resume,
+    RestartStage,
+    Quit,
+}
+
+impl MenuOption for PauseOption {
+    const ALL: &'static [Self] = &[Self::Resume, Self::RestartStage, Self::Quit];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Resume => "RESUME",
+            Self::RestartStage => "RESTART",
+            Self::Quit => "QUIT",
+        }
+    }
+}
+
+/// The state the game was paused from, which resuming returns to.
+#[derive(Resource, Deref)]
+pub struct PausedFrom(pub GameState);
+
+/// Marks that the current stage is being restarted from the pause menu, so
+/// that its bricks are respawned once the game is back to serving.
+#[derive(Resource)]
+pub struct RestartStage;
+
+/// The score and lives the current stage was started with, which restarting
+/// the stage puts back so that it can't be used to farm either.
+#[derive(Resource)]
+pub struct StageStart {
+    pub score: u32,
+    pub lives: u32,
+}
@@ -0,0 +1,12 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Present while the player waits for a new ball after losing one.
+#[derive(Resource, Deref, DerefMut)]
+pub struct RespawnTimer(pub Timer);
+
+impl Default for RespawnTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once))
+    }
+}
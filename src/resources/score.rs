@@ -0,0 +1,39 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Score(pub u32);
+
+/// Tunables for how bricks are scored and when extra lives are awarded.
+#[derive(Resource)]
+pub struct ScoreConfig {
+    /// The number of consecutive brick hits needed to raise the multiplier
+    /// by one.
+    pub combo_hits_per_step: u32,
+    pub max_multiplier: u32,
+    /// The scores at which the player is awarded an extra life.
+    pub extra_life_scores: Vec<u32>,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self {
+            combo_hits_per_step: COMBO_HITS_PER_STEP,
+            max_multiplier: COMBO_MAX_MULTIPLIER,
+            extra_life_scores: EXTRA_LIFE_SCORES.to_vec(),
+        }
+    }
+}
+
+/// The number of bricks hit since the ball last touched the paddle.
+#[derive(Resource, Default)]
+pub struct Combo {
+    pub hits: u32,
+}
+
+impl Combo {
+    pub fn multiplier(&self, config: &ScoreConfig) -> u32 {
+        let steps = self.hits.saturating_sub(1) / config.combo_hits_per_step.max(1);
+        (1 + steps).min(config.max_multiplier)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::assets::Campaign;
+use bevy::prelude::*;
+
+/// The campaign being played through.
+#[derive(Resource, Deref)]
+pub struct CurrentCampaign(pub Handle<Campaign>);
+
+/// The index of the current stage within the campaign, starting from 0.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct CurrentStage(pub usize);
+
+impl CurrentStage {
+    /// The stage number as shown to the player, starting from 1.
+    pub fn number(&self) -> usize {
+        self.0 + 1
+    }
+}
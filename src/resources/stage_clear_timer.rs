@@ -0,0 +1,12 @@
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// Present while the cleared stage is shown, before moving on to the next.
+#[derive(Resource, Deref, DerefMut)]
+pub struct StageClearTimer(pub Timer);
+
+impl Default for StageClearTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(STAGE_CLEAR_DELAY, TimerMode::Once))
+    }
+}
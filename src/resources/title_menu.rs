@@ -0,0 +1,141 @@
+use super::{Action, Menu, MenuOption};
+use crate::constants::*;
+use bevy::prelude::*;
+
+/// An option in the main menu on the title screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MainMenuOption {
+    Start,
+    StageSelect,
+    Options,
+    HighScores,
+    Quit,
+}
+
+impl MenuOption for MainMenuOption {
+    const ALL: &'static [Self] = &[
+        Self::Start,
+        Self::StageSelect,
+        Self::Options,
+        Self::HighScores,
+        Self::Quit,
+    ];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Start => "START",
+            Self::StageSelect => "STAGE SELECT",
+            Self::Options => "OPTIONS",
+            Self::HighScores => "HIGH SCORES",
+            Self::Quit => "QUIT",
+        }
+    }
+}
+
+/// An option in the options menu on the title screen.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum OptionsOption {
+    Lives,
+    Paddle,
+    Controls,
+    Back,
+}
+
+impl MenuOption for OptionsOption {
+    const ALL: &'static [Self] = &[Self::Lives, Self::Paddle, Self::Controls, Self::Back];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Lives => "LIVES",
+            Self::Paddle => "MOVE",
+            Self::Controls => "CONTROLS",
+            Self::Back => "BACK",
+        }
+    }
+}
+
+/// An option in the controls menu, from which actions are rebound.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ControlsOption {
+    Rebind(Action),
+    Back,
+}
+
+impl MenuOption for ControlsOption {
+    const ALL: &'static [Self] = &[
+        Self::Rebind(Action::MoveLeft),
+        Self::Rebind(Action::MoveRight),
+        Self::Rebind(Action::MoveUp),
+        Self::Rebind(Action::MoveDown),
+        Self::Rebind(Action::Serve),
+        Self::Rebind(Action::Pause),
+        Self::Back,
+    ];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Rebind(action) => action.label(),
+            Self::Back => "BACK",
+        }
+    }
+}
+
+/// An action waiting for the player to press the key it should be bound to.
+#[derive(Resource)]
+pub struct Rebinding {
+    pub action: Action,
+    /// The action the last key pressed is already bound to, which it can't
+    /// also be bound to.
+    pub conflict: Option<Action>,
+}
+
+impl Rebinding {
+    pub fn new(action: Action) -> Self {
+        Self {
+            action,
+            conflict: None,
+        }
+    }
+}
+
+/// How the player steers the paddle, picked from the options menu.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PaddleControl {
+    /// The paddle moves at a steady speed while a move action is held.
+    Buttons,
+    /// The paddle follows the mouse cursor across the screen.
+    Mouse,
+    /// The paddle follows the position of a gamepad axis, from one wall to
+    /// the other.
+    Analog,
+}
+
+impl MenuOption for PaddleControl {
+    const ALL: &'static [Self] = &[Self::Buttons, Self::Mouse, Self::Analog];
+
+    fn label(&self) -> &str {
+        match self {
+            Self::Buttons => "KEYS",
+            Self::Mouse => "MOUSE",
+            Self::Analog => "STICK",
+        }
+    }
+}
+
+/// Settings chosen from the options menu, which apply to each new game.
+#[derive(Resource)]
+pub struct GameOptions {
+    pub starting_lives: u32,
+    /// Cycled through like a menu of its own with the left and right move
+    /// bindings.
+    pub paddle_control: Menu<PaddleControl>,
+}
+
+impl Default for GameOptions {
+    fn default() -> Self {
+        Self {
+            starting_lives: STARTING_LIVES,
+            paddle_control: Menu::default(),
+        }
+    }
+}
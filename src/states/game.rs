@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+/// The top-level flow of the game, from the title screen through to game over.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    /// Assets needed before the game can start are still loading.
+    #[default]
+    Loading,
+    Title,
+    /// The ball is parked on the paddle, waiting to be served.
+    Serving,
+    Playing,
+    Paused,
+    StageClear,
+    GameOver,
+}
+
+/// Computed state which is active for as long as a play field exists, so that
+/// the field survives moving between serving, playing, pausing and game over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = GameState;
+
+    fn compute(state: GameState) -> Option<Self> {
+        match state {
+            GameState::Serving
+            | GameState::Playing
+            | GameState::Paused
+            | GameState::StageClear
+            | GameState::GameOver => Some(InGame),
+            GameState::Loading | GameState::Title => None,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod game;
+mod title;
+
+pub use game::*;
+pub use title::*;
@@ -0,0 +1,17 @@
+use super::GameState;
+use bevy::prelude::*;
+
+/// The screens of the title, from which a game is started.
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::Title)]
+pub enum TitleScreen {
+    /// Waiting for the player to press start.
+    #[default]
+    PressStart,
+    MainMenu,
+    StageSelect,
+    Options,
+    /// The controls menu within the options, from which actions are rebound.
+    Controls,
+    HighScores,
+}
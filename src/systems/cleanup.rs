@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+/// Despawns every entity with the component `T`, used to tear down entities
+/// when leaving the state they belong to.
+pub fn despawn_all<T: Component>(mut commands: Commands, query: Query<Entity, With<T>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Removes the resource `R`, used to discard state that should not outlive
+/// the state it was created in.
+pub fn remove_resource<R: Resource>(mut commands: Commands) {
+    commands.remove_resource::<R>();
+}
@@ -1,7 +1,13 @@
-use crate::components::{Ball, Brick, Collider, Velocity};
+use crate::components::{
+    Ball, Brick, BrickVariant, Ceiling, Collider, HitPoints, Shrunk, Velocity, Wall,
+};
 use crate::constants::*;
-use crate::events::CollisionEvent;
-use crate::{components::Paddle, resources::Keybindings};
+use crate::events::{BallLost, BrickDestroyed, CollisionEvent};
+use crate::resources::{BallSpeed, BallSpeedConfig, BrickDescent, Combo, Score, ScoreConfig};
+use crate::{
+    components::Paddle,
+    resources::{Action, ActionState, PaddleConfig},
+};
 use bevy::math::bounding::{Aabb2d, BoundingCircle, BoundingVolume, IntersectsVolume};
 use bevy::prelude::*;
 
@@ -10,25 +16,61 @@ const MOVE_RIGHT: f32 = 1.;
 const NO_MOVEMENT: f32 = 0.;
 
 pub fn handle_input(
-    keybindings: Res<Keybindings>,
-    input: Res<ButtonInput<KeyCode>>,
-    paddle: Single<&mut Transform, With<Paddle>>,
+    actions: Res<ActionState>,
+    config: Res<PaddleConfig>,
+    paddle: Single<(&mut Transform, &mut Velocity), With<Paddle>>,
     time: Res<Time>,
 ) {
-    let mut transform = paddle.into_inner();
+    let (mut transform, mut velocity) = paddle.into_inner();
+    let half_width = transform.scale.x / 2.;
+    let left_bound = PADDLE_LEFT_BOUND + half_width;
+    let right_bound = PADDLE_RIGHT_BOUND - half_width;
 
-    let new_direction = if input.any_pressed(keybindings.move_left.clone()) {
-        MOVE_LEFT
-    } else if input.any_pressed(keybindings.move_right.clone()) {
-        MOVE_RIGHT
-    } else {
-        NO_MOVEMENT
+    velocity.x = match actions.paddle_target {
+        // Chase the target rather than jumping to it, so the paddle never
+        // moves faster than the ball-angle physics were tuned for.
+        Some(target) if time.delta_secs() > 0. => {
+            let max_speed = config.max_speed;
+            let distance = target.clamp(left_bound, right_bound) - transform.translation.x;
+            (distance / time.delta_secs()).clamp(-max_speed, max_speed)
+        }
+        Some(_) => NO_MOVEMENT,
+        None => {
+            let new_direction = if actions.pressed(Action::MoveLeft) {
+                MOVE_LEFT
+            } else if actions.pressed(Action::MoveRight) {
+                MOVE_RIGHT
+            } else {
+                NO_MOVEMENT
+            };
+
+            new_direction * PADDLE_SPEED
+        }
     };
 
-    let new_paddle_position =
-        transform.translation.x + new_direction * PADDLE_SPEED * time.delta_secs();
+    let new_paddle_position = transform.translation.x + velocity.x * time.delta_secs();
 
-    transform.translation.x = new_paddle_position.clamp(PADDLE_LEFT_BOUND, PADDLE_RIGHT_BOUND);
+    transform.translation.x = new_paddle_position.clamp(left_bound, right_bound);
+}
+
+/// Keeps the ball resting on top of the paddle while it waits to be served.
+pub fn park_ball(
+    ball: Single<&mut Transform, (With<Ball>, Without<Paddle>)>,
+    paddle: Single<&Transform, With<Paddle>>,
+) {
+    let mut ball_transform = ball.into_inner();
+    ball_transform.translation.x = paddle.translation.x;
+    ball_transform.translation.y = BALL_START_POSITION.y;
+}
+
+/// Moves scrolling bricks along their rows, wrapping them back around to the
+/// other side of the brick field once they pass its edge.
+pub fn scroll_bricks(mut bricks: Query<(&mut Transform, &Velocity), With<Brick>>, time: Res<Time>) {
+    for (mut transform, velocity) in &mut bricks {
+        let x = transform.translation.x + velocity.x * time.delta_secs();
+        transform.translation.x =
+            BRICK_FIELD_LEFT + (x - BRICK_FIELD_LEFT).rem_euclid(BRICK_FIELD_WIDTH);
+    }
 }
 
 pub fn ball_movement(ball: Single<(&mut Transform, &Velocity), With<Ball>>, time: Res<Time>) {
@@ -45,17 +87,28 @@ pub enum Collision {
     Bottom,
 }
 
+type Colliders<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        Option<&'static Velocity>,
+        Has<Paddle>,
+    ),
+    (With<Collider>, Without<Ball>),
+>;
+
 // Thanks and credits to the official Bevy examples for this first-draft collision system:
 // https://github.com/bevyengine/bevy/blob/main/examples/games/breakout.rs
 pub fn check_for_collisions(
-    mut commands: Commands,
     ball: Single<(&mut Velocity, &Transform), With<Ball>>,
-    mut collider_query: Query<(Entity, &Transform, Option<&mut Brick>), With<Collider>>,
+    collider_query: Colliders,
     mut collision_events: EventWriter<CollisionEvent>,
 ) {
     let (mut velocity, ball_transform) = ball.into_inner();
 
-    for (collider_entity, collider_transform, maybe_brick) in &mut collider_query {
+    for (collider_entity, collider_transform, collider_velocity, is_paddle) in &collider_query {
         let collision = ball_collision(
             BoundingCircle::new(ball_transform.translation.truncate(), BALL_SIZE / 2.),
             Aabb2d::new(
@@ -64,32 +117,297 @@ pub fn check_for_collisions(
             ),
         );
 
-        if let Some(collision) = collision {
-            collision_events.write_default();
+        let Some(collision) = collision else {
+            continue;
+        };
+
+        let rebounded = if is_paddle && collision == Collision::Top {
+            let moving_in = velocity.y < 0.;
 
-            if maybe_brick.is_some() {
-                commands.entity(collider_entity).despawn();
+            if moving_in {
+                velocity.0 = paddle_rebound(
+                    ball_transform.translation.x,
+                    collider_transform,
+                    velocity.length(),
+                );
             }
 
-            let mut reflect_x = false;
-            let mut reflect_y = false;
+            moving_in
+        } else {
+            // Whether the ball is moving into the collider is judged relative
+            // to the collider's own movement, so that a moving collider can
+            // catch up with the ball. The ball is then sent away from the
+            // side it hit, keeping its speed.
+            let relative_velocity = velocity.0 - collider_velocity.map_or(Vec2::ZERO, |v| v.0);
 
             match collision {
-                Collision::Left => reflect_x = velocity.x > 0.,
-                Collision::Right => reflect_x = velocity.x < 0.,
-                Collision::Top => reflect_y = velocity.y < 0.,
-                Collision::Bottom => reflect_y = velocity.y > 0.,
+                Collision::Left if relative_velocity.x > 0. => velocity.x = -velocity.x.abs(),
+                Collision::Right if relative_velocity.x < 0. => velocity.x = velocity.x.abs(),
+                Collision::Top if relative_velocity.y < 0. => velocity.y = velocity.y.abs(),
+                Collision::Bottom if relative_velocity.y > 0. => velocity.y = -velocity.y.abs(),
+                _ => continue,
             }
 
-            if reflect_x {
-                velocity.x = -velocity.x;
+            true
+        };
+
+        // A ball still overlapping a collider it has already rebounded from
+        // is moving away from it, and mustn't count as hitting it again.
+        if rebounded {
+            collision_events.write(CollisionEvent {
+                entity: collider_entity,
+            });
+        }
+    }
+}
+
+/// Picks the rebound angle for the ball from the zone of the paddle it landed
+/// on, keeping the ball travelling at the same speed.
+fn paddle_rebound(ball_x: f32, paddle_transform: &Transform, speed: f32) -> Vec2 {
+    let paddle_width = paddle_transform.scale.x;
+    let paddle_left = paddle_transform.translation.x - paddle_width / 2.;
+    let hit_offset = ((ball_x - paddle_left) / paddle_width).clamp(0., 1.);
+
+    let zone_count = PADDLE_REBOUND_ANGLES.len();
+    let zone = ((hit_offset * zone_count as f32) as usize).min(zone_count - 1);
+
+    Vec2::from_angle(PADDLE_REBOUND_ANGLES[zone]) * speed
+}
+
+/// Takes a hit point from each brick the ball has collided with, despawning
+/// any brick that has none left. Explosive bricks take their breakable
+/// neighbours with them, which may in turn set off other explosive bricks.
+pub fn damage_bricks(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    mut bricks: Query<(
+        Entity,
+        &Brick,
+        &mut HitPoints,
+        &mut Sprite,
+        &mut Visibility,
+        &Transform,
+    )>,
+    mut destroyed_events: EventWriter<BrickDestroyed>,
+    asset_server: Res<AssetServer>,
+) {
+    let mut destroyed = Vec::new();
+
+    for event in collision_events.read() {
+        let Ok((_, brick, mut hit_points, mut sprite, mut visibility, _)) =
+            bricks.get_mut(event.entity)
+        else {
+            continue;
+        };
+
+        **hit_points = hit_points.saturating_sub(1);
+
+        if **hit_points == 0 {
+            destroyed.push(event.entity);
+            continue;
+        }
+
+        if let Some(path) = brick.variant.damaged_asset_path() {
+            sprite.image = asset_server.load(path);
+        }
+
+        *visibility = Visibility::Inherited;
+    }
+
+    let mut exploding = destroyed.clone();
+
+    while let Some(entity) = exploding.pop() {
+        let Ok((_, brick, _, _, _, transform)) = bricks.get(entity) else {
+            continue;
+        };
+
+        if brick.variant != BrickVariant::Explosive {
+            continue;
+        }
+
+        let center = transform.translation.truncate();
+
+        for (neighbour, _, _, _, _, transform) in &bricks {
+            let offset = (transform.translation.truncate() - center).abs();
+
+            if offset.x < BRICK_SIZE.x * 1.5
+                && offset.y < BRICK_SIZE.y * 1.5
+                && !destroyed.contains(&neighbour)
+            {
+                destroyed.push(neighbour);
+                exploding.push(neighbour);
+            }
+        }
+    }
+
+    for entity in destroyed {
+        let Ok((_, brick, ..)) = bricks.get(entity) else {
+            continue;
+        };
+
+        commands.entity(entity).despawn();
+        destroyed_events.write(BrickDestroyed {
+            variant: brick.variant,
+        });
+    }
+}
+
+/// Scores destroyed bricks by variant, multiplied by the number of bricks hit
+/// since the ball last touched the paddle.
+pub fn score_bricks(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    paddle: Query<(), With<Paddle>>,
+    walls: Query<(), With<Wall>>,
+    mut combo: ResMut<Combo>,
+    mut score: ResMut<Score>,
+    config: Res<ScoreConfig>,
+) {
+    // Bricks destroyed by this collision have already been despawned, so
+    // anything that isn't the paddle or a wall must have been a brick.
+    for event in collision_events.read() {
+        if paddle.contains(event.entity) {
+            combo.hits = 0;
+        } else if !walls.contains(event.entity) {
+            combo.hits += 1;
+        }
+    }
+
+    let multiplier = combo.multiplier(&config);
+
+    for event in destroyed_events.read() {
+        **score += event.variant.points() * multiplier;
+    }
+}
+
+/// Moves the ball up through the speed tiers as it is returned by the paddle,
+/// reaches the top wall, or breaks particular bricks.
+pub fn update_ball_speed(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    colliders: Query<(Has<Paddle>, Has<Ceiling>)>,
+    mut ball: Single<&mut Velocity, With<Ball>>,
+    mut ball_speed: ResMut<BallSpeed>,
+    config: Res<BallSpeedConfig>,
+) {
+    let mut sped_up = false;
+
+    for event in collision_events.read() {
+        let Ok((is_paddle, is_ceiling)) = colliders.get(event.entity) else {
+            continue;
+        };
+
+        if is_paddle {
+            ball_speed.paddle_hits += 1;
+
+            for &(hits, tier) in &config.paddle_hits {
+                if ball_speed.paddle_hits >= hits {
+                    sped_up |= ball_speed.raise_to(tier, &config);
+                }
             }
+        }
 
-            if reflect_y {
-                velocity.y = -velocity.y;
+        if let Some(tier) = config.top_wall.filter(|_| is_ceiling) {
+            sped_up |= ball_speed.raise_to(tier, &config);
+        }
+    }
+
+    for event in destroyed_events.read() {
+        for &(variant, tier) in &config.bricks {
+            if event.variant == variant {
+                sped_up |= ball_speed.raise_to(tier, &config);
             }
         }
     }
+
+    if sped_up {
+        ball.0 = ball.normalize_or_zero() * ball_speed.speed(&config);
+    }
+}
+
+/// Halves the paddle once the ball breaks through to the top wall. The paddle
+/// stays shrunk until the ball is lost or the stage ends.
+pub fn shrink_paddle(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    ceiling: Query<(), With<Ceiling>>,
+    paddle: Single<(Entity, &mut Transform, &mut Sprite, Has<Shrunk>), With<Paddle>>,
+    asset_server: Res<AssetServer>,
+) {
+    let hit_ceiling = collision_events
+        .read()
+        .any(|event| ceiling.contains(event.entity));
+
+    let (entity, mut transform, mut sprite, shrunk) = paddle.into_inner();
+
+    if shrunk || !hit_ceiling {
+        return;
+    }
+
+    transform.scale = PADDLE_SHRUNK_SIZE.extend(1.);
+    sprite.image = asset_server.load(PADDLE_SHRUNK_SPRITE_PATH);
+
+    commands.entity(entity).insert(Shrunk);
+}
+
+/// Steps the brick field down a row whenever the level's interval elapses or
+/// the paddle has been hit enough times. If the lowest bricks reach the
+/// paddle, the round ends as though the ball was lost and the field returns
+/// to where it started.
+pub fn descend_bricks(
+    mut collision_events: EventReader<CollisionEvent>,
+    paddle: Query<(), With<Paddle>>,
+    mut bricks: Query<&mut Transform, With<Brick>>,
+    mut descent: ResMut<BrickDescent>,
+    mut ball_lost_events: EventWriter<BallLost>,
+    time: Res<Time>,
+) {
+    let mut step = false;
+
+    if let Some(timer) = &mut descent.timer {
+        step |= timer.tick(time.delta()).just_finished();
+    }
+
+    let paddle_hits = collision_events
+        .read()
+        .filter(|event| paddle.contains(event.entity))
+        .count() as u32;
+
+    if let Some(hits_per_step) = descent.paddle_hits {
+        descent.hits_since_step += paddle_hits;
+
+        if descent.hits_since_step >= hits_per_step {
+            descent.hits_since_step = 0;
+            step = true;
+        }
+    }
+
+    if !step {
+        return;
+    }
+
+    descent.rows += 1;
+
+    for mut transform in &mut bricks {
+        transform.translation.y -= BRICK_SIZE.y;
+    }
+
+    let paddle_line = PADDLE_START_POSITION.y + PADDLE_SIZE.y / 2.;
+    let reached_paddle = bricks
+        .iter()
+        .any(|transform| transform.translation.y - BRICK_SIZE.y / 2. <= paddle_line);
+
+    if !reached_paddle {
+        return;
+    }
+
+    ball_lost_events.write_default();
+
+    for mut transform in &mut bricks {
+        transform.translation.y += descent.offset();
+    }
+
+    descent.rows = 0;
 }
 
 fn ball_collision(ball_bounds: BoundingCircle, box_bounds: Aabb2d) -> Option<Collision> {
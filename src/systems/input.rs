@@ -0,0 +1,164 @@
+use crate::components::Paddle;
+use crate::constants::*;
+use crate::resources::{
+    Action, ActionState, Binding, GameOptions, Keybindings, KeybindingsWatcher, PaddleConfig,
+    PaddleControl, StickDirection,
+};
+use bevy::{
+    input::gamepad::{GamepadConnection, GamepadConnectionEvent},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+pub fn start_action_frame(mut actions: ResMut<ActionState>) {
+    actions.start_frame();
+}
+
+pub fn finish_action_frame(mut actions: ResMut<ActionState>) {
+    actions.finish_frame();
+}
+
+/// Holds down each action with a key, gamepad button or gamepad axis bound to
+/// it that is held down on any connected device.
+pub fn read_bindings(
+    keybindings: Res<Keybindings>,
+    keys: Res<ButtonInput<KeyCode>>,
+    sticks: Res<ButtonInput<StickDirection>>,
+    gamepads: Query<&Gamepad>,
+    mut actions: ResMut<ActionState>,
+) {
+    for action in Action::ALL {
+        let held = keybindings
+            .bindings(action)
+            .iter()
+            .any(|&binding| match binding {
+                Binding::Key(key) => keys.pressed(key),
+                Binding::GamepadButton(button) => {
+                    gamepads.iter().any(|gamepad| gamepad.pressed(button))
+                }
+                Binding::GamepadAxis(direction) => sticks.pressed(direction),
+            });
+
+        if held {
+            actions.hold(action);
+        }
+    }
+}
+
+/// Holds pause down when a gamepad is disconnected mid-game, so that its
+/// player can reconnect it.
+pub fn read_gamepad_disconnects(
+    mut connection_events: EventReader<GamepadConnectionEvent>,
+    mut actions: ResMut<ActionState>,
+) {
+    if connection_events.read().any(|event| event.disconnected()) {
+        actions.hold(Action::Pause);
+    }
+}
+
+/// Steers the paddle towards the mouse cursor, while it is over the window.
+pub fn read_mouse(
+    options: Res<GameOptions>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Single<(&Camera, &GlobalTransform)>,
+    mut actions: ResMut<ActionState>,
+) {
+    if options.paddle_control.option() != PaddleControl::Mouse {
+        return;
+    }
+
+    let (camera, camera_transform) = *camera;
+
+    if let Some(position) = window
+        .cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor).ok())
+    {
+        actions.paddle_target = Some(position.x);
+    }
+}
+
+/// Steers the paddle towards the position of the paddle axis, read as an
+/// absolute position with either end of its travel putting the paddle against
+/// a wall.
+pub fn read_paddle_axis(
+    options: Res<GameOptions>,
+    config: Res<PaddleConfig>,
+    gamepads: Query<&Gamepad>,
+    paddle: Single<&Transform, With<Paddle>>,
+    mut actions: ResMut<ActionState>,
+) {
+    if options.paddle_control.option() != PaddleControl::Analog {
+        return;
+    }
+
+    if let Some(value) = gamepads
+        .iter()
+        .find_map(|gamepad| gamepad.get(config.analog_axis))
+    {
+        // The paddle's centre stops half its width short of either wall.
+        let centre = (PADDLE_LEFT_BOUND + PADDLE_RIGHT_BOUND) / 2.;
+        let half_range = (PADDLE_RIGHT_BOUND - PADDLE_LEFT_BOUND - paddle.scale.x) / 2.;
+        actions.paddle_target = Some(centre + value * half_range);
+    }
+}
+
+/// Presses and releases each direction of every gamepad axis as it is pushed
+/// past the deadzone, so that axes can be bound to actions just like buttons.
+pub fn update_stick_directions(
+    keybindings: Res<Keybindings>,
+    gamepads: Query<&Gamepad>,
+    mut sticks: ResMut<ButtonInput<StickDirection>>,
+) {
+    sticks.clear();
+
+    for direction in StickDirection::all() {
+        let pushed = gamepads.iter().any(|gamepad| {
+            gamepad
+                .get(direction.axis)
+                .is_some_and(|value| direction.is_pushed(value, keybindings.deadzone))
+        });
+
+        if pushed {
+            sticks.press(direction);
+        } else {
+            sticks.release(direction);
+        }
+    }
+}
+
+pub fn log_gamepad_connections(mut connection_events: EventReader<GamepadConnectionEvent>) {
+    for event in connection_events.read() {
+        match &event.connection {
+            GamepadConnection::Connected { name, .. } => info!("Gamepad connected: {name}"),
+            GamepadConnection::Disconnected => info!("Gamepad disconnected"),
+        }
+    }
+}
+
+/// Reloads the keybindings whenever their file changes, over the top of the
+/// defaults just as at startup, so that the file alone decides the bindings.
+/// A file which can't be parsed leaves the current keybindings in place until
+/// it is fixed.
+pub fn reload_keybindings(
+    mut watcher: ResMut<KeybindingsWatcher>,
+    mut keybindings: ResMut<Keybindings>,
+    time: Res<Time<Real>>,
+) {
+    if !watcher.timer.tick(time.delta()).just_finished() || !watcher.check(KEYBINDINGS_PATH) {
+        return;
+    }
+
+    match Keybindings::load(KEYBINDINGS_PATH, &Keybindings::default()) {
+        Ok((new_keybindings, warnings)) => {
+            for warning in warnings {
+                warn!("{KEYBINDINGS_PATH}:{warning}");
+            }
+
+            *keybindings = new_keybindings;
+            info!("Reloaded keybindings from {KEYBINDINGS_PATH}");
+        }
+        Err(err) => {
+            warn!("Couldn't reload keybindings. Keeping the current ones. {err:?}");
+        }
+    }
+}
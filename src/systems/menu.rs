@@ -0,0 +1,444 @@
+use crate::assets::{Campaign, Level};
+use crate::components::{BitmapText, Logo, PauseMenuText, PausePanel, TextAlign, TitleText};
+use crate::constants::*;
+use crate::resources::{
+    Action, ActionState, Binding, ControlsOption, CurrentCampaign, CurrentStage, GameOptions,
+    HighScores, Keybindings, MainMenuOption, Menu, MenuOption, OptionsOption, PauseOption,
+    PausedFrom, Rebinding, RestartStage, StickDirection,
+};
+use crate::states::{GameState, TitleScreen};
+use bevy::prelude::*;
+
+type TitleTextQuery<'w> =
+    Single<'w, (&'static mut BitmapText, &'static mut Transform), With<TitleText>>;
+
+/// Replaces the title text with that of the current title screen.
+fn show_title_text(title_text: TitleTextQuery, text: BitmapText, position: Vec2) {
+    let (mut title_text, mut transform) = title_text.into_inner();
+    *title_text = text;
+    transform.translation = position.extend(0.);
+}
+
+/// Moves the selection of a menu up and down with the move bindings.
+pub fn navigate_menu<T: MenuOption>(actions: Res<ActionState>, mut menu: ResMut<Menu<T>>) {
+    if actions.just_pressed(Action::MoveUp) {
+        menu.previous();
+    }
+
+    if actions.just_pressed(Action::MoveDown) {
+        menu.next();
+    }
+}
+
+pub fn spawn_title(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn(Logo::new(&asset_server));
+    commands.spawn(TitleText);
+}
+
+pub fn show_press_start(title_text: TitleTextQuery) {
+    show_title_text(
+        title_text,
+        BitmapText::new("PRESS START").with_align(TextAlign::Center),
+        PRESS_START_POSITION,
+    );
+}
+
+pub fn press_start(actions: Res<ActionState>, mut next_screen: ResMut<NextState<TitleScreen>>) {
+    if actions.just_pressed(Action::Serve) {
+        next_screen.set(TitleScreen::MainMenu);
+    }
+}
+
+/// Returns to the main menu from one of the screens it leads to.
+pub fn back_to_main_menu(
+    actions: Res<ActionState>,
+    mut next_screen: ResMut<NextState<TitleScreen>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_screen.set(TitleScreen::MainMenu);
+    }
+}
+
+/// Starting a game from the main menu always starts from the first stage.
+pub fn reset_stage(mut stage: ResMut<CurrentStage>) {
+    *stage = CurrentStage::default();
+}
+
+pub fn show_main_menu(menu: Res<Menu<MainMenuOption>>, title_text: TitleTextQuery) {
+    show_title_text(
+        title_text,
+        BitmapText::new(menu.text()),
+        TITLE_MENU_POSITION,
+    );
+}
+
+pub fn choose_main_menu_option(
+    actions: Res<ActionState>,
+    menu: Res<Menu<MainMenuOption>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut next_screen: ResMut<NextState<TitleScreen>>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_screen.set(TitleScreen::PressStart);
+        return;
+    }
+
+    if !actions.just_pressed(Action::Serve) {
+        return;
+    }
+
+    match menu.option() {
+        MainMenuOption::Start => next_state.set(GameState::Serving),
+        MainMenuOption::StageSelect => next_screen.set(TitleScreen::StageSelect),
+        MainMenuOption::Options => next_screen.set(TitleScreen::Options),
+        MainMenuOption::HighScores => next_screen.set(TitleScreen::HighScores),
+        MainMenuOption::Quit => {
+            app_exit_events.write(AppExit::Success);
+        }
+    }
+}
+
+/// Picks a stage of the campaign to start from with the left and right move
+/// bindings.
+pub fn select_stage(
+    actions: Res<ActionState>,
+    campaign: Res<CurrentCampaign>,
+    campaigns: Res<Assets<Campaign>>,
+    mut stage: ResMut<CurrentStage>,
+) {
+    let Some(campaign) = campaigns.get(&**campaign) else {
+        return;
+    };
+
+    let stage_count = campaign.levels.len();
+
+    if actions.just_pressed(Action::MoveLeft) {
+        **stage = (**stage + stage_count - 1) % stage_count;
+    }
+
+    if actions.just_pressed(Action::MoveRight) {
+        **stage = (**stage + 1) % stage_count;
+    }
+}
+
+pub fn show_stage_select(
+    stage: Res<CurrentStage>,
+    campaign: Res<CurrentCampaign>,
+    campaigns: Res<Assets<Campaign>>,
+    levels: Res<Assets<Level>>,
+    title_text: TitleTextQuery,
+) {
+    let name = campaigns
+        .get(&**campaign)
+        .and_then(|campaign| campaign.levels.get(**stage))
+        .and_then(|level| levels.get(level))
+        .map_or("", |level| &level.name);
+
+    let number = BitmapText::number(stage.number() as u32, HUD_COUNTER_DIGITS);
+    let text = format!("STAGE SELECT\n\n< {number} >\n{name}");
+
+    show_title_text(
+        title_text,
+        BitmapText::new(text).with_align(TextAlign::Center),
+        STAGE_SELECT_POSITION,
+    );
+}
+
+pub fn start_from_stage(actions: Res<ActionState>, mut next_state: ResMut<NextState<GameState>>) {
+    if actions.just_pressed(Action::Serve) {
+        next_state.set(GameState::Serving);
+    }
+}
+
+/// Changes the value of the selected option with the left and right move
+/// bindings.
+pub fn change_option(
+    actions: Res<ActionState>,
+    menu: Res<Menu<OptionsOption>>,
+    mut options: ResMut<GameOptions>,
+) {
+    match menu.option() {
+        OptionsOption::Lives => {
+            if actions.just_pressed(Action::MoveLeft) {
+                options.starting_lives = options
+                    .starting_lives
+                    .saturating_sub(1)
+                    .max(MIN_STARTING_LIVES);
+            }
+
+            if actions.just_pressed(Action::MoveRight) {
+                options.starting_lives = (options.starting_lives + 1).min(MAX_STARTING_LIVES);
+            }
+        }
+        OptionsOption::Paddle => {
+            if actions.just_pressed(Action::MoveLeft) {
+                options.paddle_control.previous();
+            }
+
+            if actions.just_pressed(Action::MoveRight) {
+                options.paddle_control.next();
+            }
+        }
+        OptionsOption::Controls | OptionsOption::Back => {}
+    }
+}
+
+pub fn show_options(
+    menu: Res<Menu<OptionsOption>>,
+    options: Res<GameOptions>,
+    title_text: TitleTextQuery,
+) {
+    let text = menu.text_with(|option| match option {
+        OptionsOption::Lives => format!("{} < {} >", option.label(), options.starting_lives),
+        OptionsOption::Paddle => {
+            format!(
+                "{} < {} >",
+                option.label(),
+                options.paddle_control.option().label()
+            )
+        }
+        OptionsOption::Controls | OptionsOption::Back => option.label().to_string(),
+    });
+
+    show_title_text(title_text, BitmapText::new(text), TITLE_MENU_POSITION);
+}
+
+pub fn choose_option(
+    actions: Res<ActionState>,
+    menu: Res<Menu<OptionsOption>>,
+    mut next_screen: ResMut<NextState<TitleScreen>>,
+) {
+    if !actions.just_pressed(Action::Serve) {
+        return;
+    }
+
+    match menu.option() {
+        OptionsOption::Lives | OptionsOption::Paddle => {}
+        OptionsOption::Controls => next_screen.set(TitleScreen::Controls),
+        OptionsOption::Back => next_screen.set(TitleScreen::MainMenu),
+    }
+}
+
+/// A short name for a binding, as shown on the controls screen.
+fn binding_name(binding: Binding) -> String {
+    let name = binding.to_string();
+    let name = ["Key", "Digit", "Gamepad:"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(&name);
+
+    name.chars().take(BINDING_NAME_LENGTH).collect()
+}
+
+/// Lists each action beside the first key bound to it, or its first gamepad
+/// input if it has no keys, along with a prompt while an action is being
+/// rebound.
+pub fn show_controls(
+    menu: Res<Menu<ControlsOption>>,
+    keybindings: Res<Keybindings>,
+    rebinding: Option<Res<Rebinding>>,
+    title_text: TitleTextQuery,
+) {
+    let options = menu.text_with(|option| match option {
+        ControlsOption::Rebind(action) => {
+            let bindings = keybindings.bindings(action);
+            let binding = bindings
+                .iter()
+                .find(|binding| !binding.is_gamepad())
+                .or(bindings.first())
+                .map_or(String::from("-"), |&binding| binding_name(binding));
+
+            format!("{:<6}{binding}", option.label())
+        }
+        ControlsOption::Back => option.label().to_string(),
+    });
+
+    let prompt = match rebinding.as_deref() {
+        Some(Rebinding {
+            conflict: Some(action),
+            ..
+        }) => format!("IN USE: {}", action.label()),
+        Some(_) => String::from("PRESS A KEY\nPAUSE TO CANCEL"),
+        None => String::new(),
+    };
+
+    show_title_text(
+        title_text,
+        BitmapText::new(format!("CONTROLS\n\n{options}\n\n{prompt}")),
+        CONTROLS_POSITION,
+    );
+}
+
+/// Chooses an action to rebind with the serve binding, or goes back to the
+/// options with the pause binding.
+pub fn choose_control(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    menu: Res<Menu<ControlsOption>>,
+    mut next_screen: ResMut<NextState<TitleScreen>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_screen.set(TitleScreen::Options);
+        return;
+    }
+
+    if !actions.just_pressed(Action::Serve) {
+        return;
+    }
+
+    match menu.option() {
+        ControlsOption::Rebind(action) => commands.insert_resource(Rebinding::new(action)),
+        ControlsOption::Back => next_screen.set(TitleScreen::Options),
+    }
+}
+
+/// Binds the action being rebound to the next key, gamepad button or gamepad
+/// axis pressed, then saves the keybindings. A key replaces the action's keys
+/// and a gamepad input replaces its gamepad inputs, so that rebinding one
+/// doesn't lose the other. An input already bound to another action is
+/// refused, while one already bound to the same action leaves its bindings as
+/// they were. An input bound to pause cancels the rebinding instead.
+pub fn rebind_action(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    sticks: Res<ButtonInput<StickDirection>>,
+    gamepads: Query<&Gamepad>,
+    mut keybindings: ResMut<Keybindings>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    // The input that chose the action to rebind was pressed this frame.
+    if rebinding.is_added() {
+        return;
+    }
+
+    // Keys and buttons the platform can't identify have no name to save them
+    // under.
+    let pressed = keys
+        .get_just_pressed()
+        .filter(|key| !matches!(key, KeyCode::Unidentified(_)))
+        .map(|&key| Binding::Key(key))
+        .chain(
+            gamepads
+                .iter()
+                .flat_map(|gamepad| gamepad.get_just_pressed())
+                .filter(|button| !matches!(button, GamepadButton::Other(_)))
+                .map(|&button| Binding::GamepadButton(button)),
+        )
+        .chain(
+            sticks
+                .get_just_pressed()
+                .map(|&direction| Binding::GamepadAxis(direction)),
+        )
+        .next();
+
+    let Some(binding) = pressed else {
+        return;
+    };
+
+    match keybindings.action_for(binding) {
+        Some(action) if action == rebinding.action || action == Action::Pause => {}
+        Some(action) => {
+            rebinding.conflict = Some(action);
+            return;
+        }
+        None => {
+            info!("Binding {} to {binding}", rebinding.action.name());
+
+            let bindings = keybindings.bindings_mut(rebinding.action);
+            bindings.retain(|bound| bound.is_gamepad() != binding.is_gamepad());
+            bindings.push(binding);
+
+            if let Err(err) = keybindings.save(KEYBINDINGS_PATH) {
+                warn!("Couldn't save keybindings. {err:?}");
+            }
+        }
+    }
+
+    commands.remove_resource::<Rebinding>();
+}
+
+pub fn show_high_scores(high_scores: Res<HighScores>, title_text: TitleTextQuery) {
+    show_title_text(
+        title_text,
+        BitmapText::new(high_scores.table("HIGH SCORES")).with_align(TextAlign::Center),
+        TITLE_HIGH_SCORES_POSITION,
+    );
+}
+
+pub fn close_high_scores(
+    actions: Res<ActionState>,
+    mut next_screen: ResMut<NextState<TitleScreen>>,
+) {
+    if actions.just_pressed(Action::Serve) {
+        next_screen.set(TitleScreen::MainMenu);
+    }
+}
+
+/// Pauses the game, remembering whether the ball was being served or in play
+/// so that resuming can return to it.
+pub fn pause_game(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        commands.insert_resource(PausedFrom(*state.get()));
+        commands.insert_resource(Menu::<PauseOption>::default());
+        next_state.set(GameState::Paused);
+    }
+}
+
+/// Freezes virtual time, which stops `FixedUpdate` and every timer in the
+/// game until it is unpaused.
+pub fn pause_time(mut time: ResMut<Time<Virtual>>) {
+    time.pause();
+}
+
+pub fn unpause_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+pub fn show_pause_menu(mut commands: Commands, menu: Res<Menu<PauseOption>>) {
+    commands.spawn(PausePanel::new()).with_children(|panel| {
+        panel.spawn(PausePanel::title());
+        panel.spawn(PauseMenuText::new(menu.text()));
+    });
+}
+
+pub fn update_pause_menu(
+    menu: Res<Menu<PauseOption>>,
+    mut text: Single<&mut BitmapText, With<PauseMenuText>>,
+) {
+    text.text = menu.text();
+}
+
+/// Chooses a pause menu option with the serve binding. The pause binding
+/// resumes straight away.
+pub fn choose_pause_option(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    menu: Res<Menu<PauseOption>>,
+    paused_from: Res<PausedFrom>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if actions.just_pressed(Action::Pause) {
+        next_state.set(**paused_from);
+        return;
+    }
+
+    if !actions.just_pressed(Action::Serve) {
+        return;
+    }
+
+    match menu.option() {
+        PauseOption::Resume => next_state.set(**paused_from),
+        PauseOption::RestartStage => {
+            info!("Restarting stage");
+            commands.insert_resource(RestartStage);
+            next_state.set(GameState::Serving);
+        }
+        PauseOption::Quit => next_state.set(GameState::Title),
+    }
+}
@@ -1,2 +1,6 @@
+pub mod cleanup;
 pub mod fixed_update;
+pub mod input;
+pub mod menu;
 pub mod startup;
+pub mod update;
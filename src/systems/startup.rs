@@ -1,6 +1,14 @@
 use crate::{
-    components::{Ball, Brick, BrickVariant, CornerLocation, Paddle, Wall, WallLocation},
+    assets::{Campaign, Level, LevelKind},
+    components::{
+        Ball, Brick, Ceiling, CornerLocation, Countdown, HitPoints, Hud, HudField, Paddle,
+        Velocity, Wall, WallLocation,
+    },
     constants::*,
+    resources::{
+        BallSpeedConfig, BitmapFont, BonusStage, BrickDescent, CurrentCampaign, CurrentLevel,
+        CurrentStage, PaddleConfig,
+    },
 };
 use bevy::prelude::*;
 
@@ -35,24 +43,127 @@ pub fn spawn_walls(mut commands: Commands, asset_server: Res<AssetServer>) {
     ));
 
     // spawn top wall
-    commands.spawn(Wall::with_collision(WallLocation::Top, &asset_server));
+    commands.spawn((
+        Wall::with_collision(WallLocation::Top, &asset_server),
+        Ceiling,
+    ));
+}
+
+pub fn load_font(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let layout = TextureAtlasLayout::from_grid(
+        UVec2::splat(FONT_GLYPH_SIZE as u32),
+        FONT_COLUMNS,
+        FONT_ROWS,
+        None,
+        None,
+    );
+
+    commands.insert_resource(BitmapFont {
+        image: asset_server.load(FONT_SPRITE_PATH),
+        layout: layouts.add(layout),
+    });
 }
 
-pub fn spawn_bricks(mut commands: Commands, asset_server: Res<AssetServer>) {
-    for (index, variant) in DEBUG_LEVEL.iter().enumerate() {
-        if let Some(variant) = BrickVariant::from_u8(*variant) {
-            let horizontal_tile = (index % BRICK_COLUMN_COUNT) as f32;
-            let vertical_tile = (index / BRICK_COLUMN_COUNT) as f32;
+pub fn load_campaign(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CurrentCampaign(asset_server.load(CAMPAIGN_PATH)));
+}
+
+/// Starts the campaign from the stage chosen on the title screen.
+pub fn start_campaign(
+    mut commands: Commands,
+    campaign: Res<CurrentCampaign>,
+    campaigns: Res<Assets<Campaign>>,
+    mut stage: ResMut<CurrentStage>,
+) {
+    let Some(campaign) = campaigns.get(&**campaign) else {
+        return;
+    };
+
+    if **stage >= campaign.levels.len() {
+        *stage = CurrentStage::default();
+    }
 
-            let mut position = BRICK_SPAWN_LOCATION;
-            position.x += horizontal_tile * BRICK_SIZE.x;
-            position.y -= vertical_tile * BRICK_SIZE.y;
+    commands.insert_resource(CurrentLevel(campaign.levels[**stage].clone()));
+}
+
+pub fn apply_level_params(
+    mut commands: Commands,
+    level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+    mut ball_speed_config: ResMut<BallSpeedConfig>,
+    mut paddle_config: ResMut<PaddleConfig>,
+) {
+    let Some(level) = levels.get(&**level) else {
+        return;
+    };
+
+    *ball_speed_config = BallSpeedConfig::default();
+
+    if let Some(ball_speeds) = &level.params.ball_speeds {
+        ball_speed_config.tiers = ball_speeds.clone();
+    }
+
+    *paddle_config = PaddleConfig::default();
+
+    if let Some(max_speed) = level.params.max_paddle_speed {
+        paddle_config.max_speed = max_speed;
+    }
 
-            commands.spawn(Brick::new(variant, position, &asset_server));
+    match &level.params.descend {
+        Some(descend) => commands.insert_resource(BrickDescent::new(descend)),
+        None => commands.remove_resource::<BrickDescent>(),
+    }
+
+    match level.kind {
+        LevelKind::Bonus => commands.insert_resource(BonusStage::new(&level.params.bonus)),
+        LevelKind::Stage => commands.remove_resource::<BonusStage>(),
+    }
+}
+
+pub fn spawn_countdown(mut commands: Commands, bonus_stage: Res<BonusStage>) {
+    commands.spawn(Countdown::new(bonus_stage.seconds_remaining()));
+}
+
+pub fn spawn_bricks(
+    mut commands: Commands,
+    level: Res<CurrentLevel>,
+    levels: Res<Assets<Level>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(level) = levels.get(&**level) else {
+        return;
+    };
+
+    info!("Spawning level \"{}\"", level.name);
+
+    for (row, column, variant) in level.bricks() {
+        let mut position = BRICK_SPAWN_LOCATION;
+        position.x += column as f32 * BRICK_SIZE.x;
+        position.y -= row as f32 * BRICK_SIZE.y;
+
+        let mut brick = commands.spawn(Brick::new(variant, position, &asset_server));
+
+        if let Some(hit_points) = variant.hit_points() {
+            brick.insert(HitPoints(hit_points));
+        }
+
+        if let Some(scroll) = &level.params.scroll {
+            brick.insert(Velocity(Vec2::new(scroll.row_velocity(row), 0.)));
         }
     }
 }
 
+pub fn spawn_hud(mut commands: Commands) {
+    for field in HudField::ALL {
+        commands.spawn(Hud::label(field));
+        commands.spawn(Hud::value(field));
+    }
+}
+
 pub fn spawn_paddle(mut commands: Commands, asset_server: Res<AssetServer>) {
     commands.spawn(Paddle::new(&asset_server));
 }
@@ -0,0 +1,442 @@
+use crate::assets::Campaign;
+use crate::components::{
+    Ball, BitmapText, Brick, Countdown, GameOverPanel, GameOverText, HitPoints, HudField, Paddle,
+    Shrunk, TextAlign, Velocity,
+};
+use crate::constants::*;
+use crate::events::{BallLost, BrickDestroyed};
+use crate::resources::{
+    Action, ActionState, BallSpeed, BallSpeedConfig, BitmapFont, BonusStage, Combo,
+    CurrentCampaign, CurrentLevel, CurrentStage, GameOptions, HighScore, HighScoreEntry,
+    HighScores, InitialsEntry, Lives, RespawnTimer, Score, ScoreConfig, StageClearTimer,
+    StageStart,
+};
+use crate::states::GameState;
+use bevy::asset::RecursiveDependencyLoadState;
+use bevy::prelude::*;
+
+/// Moves on to the title screen once the campaign and all of its levels have
+/// loaded. Bevy logs the reason an asset failed to load, so there is nothing
+/// more to do than exit.
+pub fn finish_loading(
+    campaign: Res<CurrentCampaign>,
+    asset_server: Res<AssetServer>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    match asset_server.recursive_dependency_load_state(&**campaign) {
+        RecursiveDependencyLoadState::Loaded => next_state.set(GameState::Title),
+        RecursiveDependencyLoadState::Failed(_) => {
+            error!("Couldn't load the campaign. Exiting.");
+            app_exit_events.write(AppExit::error());
+        }
+        _ => {}
+    }
+}
+
+/// Stops the ball so that it can be parked on the paddle until it is served.
+pub fn reset_ball(mut ball: Query<&mut Velocity, With<Ball>>) {
+    for mut velocity in &mut ball {
+        velocity.0 = Vec2::ZERO;
+    }
+}
+
+/// Restores a shrunk paddle to its full size for the next ball.
+pub fn restore_paddle(
+    mut commands: Commands,
+    paddle: Single<(Entity, &mut Transform, &mut Sprite, Has<Shrunk>), With<Paddle>>,
+    asset_server: Res<AssetServer>,
+) {
+    let (entity, mut transform, mut sprite, shrunk) = paddle.into_inner();
+
+    if !shrunk {
+        return;
+    }
+
+    transform.scale = PADDLE_SIZE.extend(1.);
+    sprite.image = asset_server.load(PADDLE_SPRITE_PATH);
+
+    commands.entity(entity).remove::<Shrunk>();
+}
+
+/// Launches the ball off the paddle, towards whichever side the paddle is
+/// currently moving. A stationary paddle serves to the right.
+pub fn serve_ball(
+    actions: Res<ActionState>,
+    mut ball: Single<&mut Velocity, (With<Ball>, Without<Paddle>)>,
+    paddle: Single<&Velocity, With<Paddle>>,
+    ball_speed: Res<BallSpeed>,
+    ball_speed_config: Res<BallSpeedConfig>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !actions.just_pressed(Action::Serve) {
+        return;
+    }
+
+    let direction = if paddle.x < 0. { -1. } else { 1. };
+    ball.0 = Vec2::new(direction, 1.).normalize() * ball_speed.speed(&ball_speed_config);
+
+    next_state.set(GameState::Playing);
+}
+
+pub fn return_to_title(actions: Res<ActionState>, mut next_state: ResMut<NextState<GameState>>) {
+    if actions.just_pressed(Action::Serve) {
+        next_state.set(GameState::Title);
+    }
+}
+
+/// Shows the game over panel, asking for initials first if the score has
+/// earned a place in the high score table.
+pub fn show_game_over(mut commands: Commands, score: Res<Score>, high_scores: Res<HighScores>) {
+    let text = if high_scores.qualifies(**score) {
+        let entry = InitialsEntry::default();
+        let text = GameOverText::initials_prompt(&entry.text());
+        commands.insert_resource(entry);
+        text
+    } else {
+        high_scores.table("GAME OVER")
+    };
+
+    commands
+        .spawn(GameOverPanel::new())
+        .with_child(GameOverText::new(text));
+}
+
+/// Picks each letter of the initials for a new high score with the move
+/// bindings, confirming it with the serve binding. Once every letter has been
+/// entered the score is added to the table, which is saved straight away.
+pub fn enter_initials(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut entry: ResMut<InitialsEntry>,
+    mut high_scores: ResMut<HighScores>,
+    score: Res<Score>,
+    stage: Res<CurrentStage>,
+    mut text: Single<&mut BitmapText, With<GameOverText>>,
+) {
+    if actions.just_pressed(Action::MoveLeft) {
+        entry.previous_letter();
+    }
+
+    if actions.just_pressed(Action::MoveRight) {
+        entry.next_letter();
+    }
+
+    if actions.just_pressed(Action::Serve) && entry.confirm() {
+        high_scores.insert(HighScoreEntry::new(
+            entry.initials.clone(),
+            **score,
+            stage.number(),
+        ));
+
+        if let Err(err) = high_scores.save(HIGH_SCORES_PATH) {
+            warn!("Couldn't save high scores. {err:?}");
+        }
+
+        text.text = high_scores.table("HIGH SCORES");
+        commands.remove_resource::<InitialsEntry>();
+        return;
+    }
+
+    if entry.is_changed() {
+        text.text = GameOverText::initials_prompt(&entry.text());
+    }
+}
+
+pub fn reset_lives(mut lives: ResMut<Lives>, options: Res<GameOptions>) {
+    **lives = options.starting_lives;
+}
+
+pub fn reset_score(mut score: ResMut<Score>) {
+    *score = Score::default();
+}
+
+pub fn record_stage_start(mut commands: Commands, score: Res<Score>, lives: Res<Lives>) {
+    commands.insert_resource(StageStart {
+        score: **score,
+        lives: **lives,
+    });
+}
+
+pub fn restore_stage_start(
+    stage_start: Res<StageStart>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+) {
+    **score = stage_start.score;
+    **lives = stage_start.lives;
+}
+
+pub fn reset_combo(mut combo: ResMut<Combo>) {
+    *combo = Combo::default();
+}
+
+/// Awards an extra life for each threshold in the score config the score
+/// has passed since it was last checked.
+pub fn award_extra_lives(
+    score: Res<Score>,
+    config: Res<ScoreConfig>,
+    mut lives: ResMut<Lives>,
+    mut previous_score: Local<u32>,
+) {
+    // The score only goes down when it is reset for a new game or put back
+    // for a restarted stage, neither of which should earn the lives the
+    // player already had for it.
+    if **score < *previous_score {
+        *previous_score = **score;
+    }
+
+    let earned = config
+        .extra_life_scores
+        .iter()
+        .filter(|&&threshold| *previous_score < threshold && threshold <= **score)
+        .count() as u32;
+
+    if earned > 0 {
+        info!("Extra life awarded");
+        **lives += earned;
+    }
+
+    *previous_score = **score;
+}
+
+pub fn reset_ball_speed(mut ball_speed: ResMut<BallSpeed>) {
+    *ball_speed = BallSpeed::default();
+}
+
+pub fn check_for_ball_loss(
+    ball: Single<&Transform, With<Ball>>,
+    mut ball_lost_events: EventWriter<BallLost>,
+) {
+    if ball.translation.y < BALL_DEATH_ZONE {
+        ball_lost_events.write_default();
+    }
+}
+
+/// Takes a life for a lost ball, then either waits to respawn the ball or ends
+/// the game once no lives remain.
+pub fn lose_life(
+    mut commands: Commands,
+    mut ball_lost_events: EventReader<BallLost>,
+    mut lives: ResMut<Lives>,
+    mut ball: Single<&mut Visibility, With<Ball>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if ball_lost_events.is_empty() {
+        return;
+    }
+
+    ball_lost_events.clear();
+    **lives = lives.saturating_sub(1);
+
+    if **lives == 0 {
+        next_state.set(GameState::GameOver);
+        return;
+    }
+
+    **ball = Visibility::Hidden;
+    commands.insert_resource(RespawnTimer::default());
+    next_state.set(GameState::Serving);
+}
+
+/// Counts down the respawn delay, revealing the ball on the paddle once it
+/// has elapsed.
+pub fn respawn_ball(
+    mut commands: Commands,
+    mut timer: ResMut<RespawnTimer>,
+    mut ball: Single<&mut Visibility, With<Ball>>,
+    time: Res<Time>,
+) {
+    if timer.tick(time.delta()).finished() {
+        **ball = Visibility::Inherited;
+        commands.remove_resource::<RespawnTimer>();
+    }
+}
+
+pub fn check_for_stage_clear(
+    bricks: Query<(), (With<Brick>, With<HitPoints>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if bricks.is_empty() {
+        next_state.set(GameState::StageClear);
+    }
+}
+
+pub fn start_stage_clear_timer(mut commands: Commands) {
+    commands.insert_resource(StageClearTimer::default());
+}
+
+/// Once the cleared stage has been shown for long enough, moves on to the
+/// next stage of the campaign, or ends the game if it was the last.
+pub fn advance_stage(
+    mut commands: Commands,
+    mut timer: ResMut<StageClearTimer>,
+    campaign: Res<CurrentCampaign>,
+    campaigns: Res<Assets<Campaign>>,
+    mut stage: ResMut<CurrentStage>,
+    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+) {
+    if !timer.tick(time.delta()).finished() {
+        return;
+    }
+
+    commands.remove_resource::<StageClearTimer>();
+
+    let next_level = campaigns
+        .get(&**campaign)
+        .and_then(|campaign| campaign.levels.get(**stage + 1));
+
+    let Some(next_level) = next_level else {
+        info!("Campaign complete");
+        next_state.set(GameState::GameOver);
+        return;
+    };
+
+    **stage += 1;
+    info!("Starting stage {}", stage.number());
+
+    commands.insert_resource(CurrentLevel(next_level.clone()));
+    next_state.set(GameState::Serving);
+}
+
+/// Counts down the bonus stage, ending it once time runs out or the ball is
+/// lost. A ball lost during a bonus stage doesn't cost a life.
+pub fn update_bonus_stage(
+    mut bonus_stage: ResMut<BonusStage>,
+    mut ball_lost_events: EventReader<BallLost>,
+    mut countdown: Query<&mut BitmapText, With<Countdown>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    time: Res<Time>,
+) {
+    bonus_stage.timer.tick(time.delta());
+
+    let seconds = Countdown::text(bonus_stage.seconds_remaining());
+
+    for mut text in &mut countdown {
+        if text.text != seconds {
+            text.text = seconds.clone();
+        }
+    }
+
+    let ball_lost = !ball_lost_events.is_empty();
+    ball_lost_events.clear();
+
+    if ball_lost || bonus_stage.timer.finished() {
+        next_state.set(GameState::StageClear);
+    }
+}
+
+pub fn score_bonus_bricks(
+    mut destroyed_events: EventReader<BrickDestroyed>,
+    bonus_stage: Res<BonusStage>,
+    mut score: ResMut<Score>,
+) {
+    for _ in destroyed_events.read() {
+        **score += bonus_stage.brick_score;
+    }
+}
+
+/// Wraps up a bonus stage, awarding the perfect bonus if every brick was
+/// destroyed before it ended.
+pub fn finish_bonus_stage(
+    mut commands: Commands,
+    bonus_stage: Res<BonusStage>,
+    bricks: Query<(), (With<Brick>, With<HitPoints>)>,
+    countdown: Query<Entity, With<Countdown>>,
+    mut score: ResMut<Score>,
+) {
+    if bricks.is_empty() {
+        info!("Perfect bonus stage");
+        **score += bonus_stage.perfect_score;
+    }
+
+    for entity in &countdown {
+        commands.entity(entity).despawn();
+    }
+
+    commands.remove_resource::<BonusStage>();
+}
+
+pub fn update_high_score(score: Res<Score>, mut high_score: ResMut<HighScore>) {
+    if **score > **high_score {
+        **high_score = **score;
+    }
+}
+
+/// Refreshes each HUD value when the resource it shows changes.
+pub fn update_hud(
+    mut fields: Query<(Ref<HudField>, &mut BitmapText)>,
+    high_score: Res<HighScore>,
+    score: Res<Score>,
+    stage: Res<CurrentStage>,
+    lives: Res<Lives>,
+) {
+    for (field, mut text) in &mut fields {
+        let changed = match *field {
+            HudField::HighScore => high_score.is_changed(),
+            HudField::Score => score.is_changed(),
+            HudField::Stage => stage.is_changed(),
+            HudField::Lives => lives.is_changed(),
+        };
+
+        if !changed && !field.is_added() {
+            continue;
+        }
+
+        let value = match *field {
+            HudField::HighScore => **high_score,
+            HudField::Score => **score,
+            HudField::Stage => stage.number() as u32,
+            HudField::Lives => **lives,
+        };
+
+        text.text = BitmapText::number(value, field.digits());
+    }
+}
+
+/// Lays out the glyph sprites of any bitmap text that has changed, replacing
+/// the glyphs it had before.
+pub fn layout_bitmap_text(
+    mut commands: Commands,
+    font: Res<BitmapFont>,
+    texts: Query<(Entity, &BitmapText), Changed<BitmapText>>,
+) {
+    for (entity, text) in &texts {
+        let mut entity = commands.entity(entity);
+        entity.despawn_related::<Children>();
+
+        for (row, line) in text.text.lines().enumerate() {
+            let width = line.chars().count() as f32 * FONT_GLYPH_SIZE;
+            let left = match text.align {
+                TextAlign::Left => 0.,
+                TextAlign::Center => -width / 2.,
+                TextAlign::Right => -width,
+            };
+
+            for (column, character) in line.chars().enumerate() {
+                if character == ' ' {
+                    continue;
+                }
+
+                let position = Vec2::new(
+                    left + (column as f32 + 0.5) * FONT_GLYPH_SIZE,
+                    -(row as f32) * FONT_GLYPH_SIZE,
+                );
+
+                let sprite = Sprite {
+                    color: text.tone.color(),
+                    ..Sprite::from_atlas_image(
+                        font.image.clone(),
+                        TextureAtlas {
+                            layout: font.layout.clone(),
+                            index: BitmapFont::glyph_index(character),
+                        },
+                    )
+                };
+
+                entity.with_child((sprite, Transform::from_translation(position.extend(0.))));
+            }
+        }
+    }
+}